@@ -10,6 +10,9 @@ pub const DEFAULT_ROOT_WINDOW: u16 = 64;
 /// Maximum root window size
 pub const MAX_ROOT_WINDOW: u16 = 256;
 
+/// Default maximum pause duration for withdrawals, in seconds (7 days)
+pub const DEFAULT_MAX_PAUSE_DURATION: i64 = 7 * 24 * 60 * 60;
+
 /// BN254 scalar field modulus
 pub const BN254_SCALAR_FIELD: &str =
     "21888242871839275222246405745257275088548364400416034343698204186575808495617";
@@ -20,21 +23,84 @@ pub const VK_SEED: &[u8] = b"vk";
 pub const ROOTS_SEED: &[u8] = b"roots";
 pub const NULLIFIERS_SEED: &[u8] = b"nullifiers";
 pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const MULTISIG_SEED: &[u8] = b"multisig";
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+pub const ATTESTERS_SEED: &[u8] = b"attesters";
+pub const PENDING_ROOT_SEED: &[u8] = b"pending_root";
+pub const COMMITMENT_QUEUE_SEED: &[u8] = b"commitment_queue";
+pub const ASSOCIATION_ROOTS_SEED: &[u8] = b"association_roots";
+pub const DENY_LIST_SEED: &[u8] = b"deny_list";
+pub const CREDENTIAL_SEED: &[u8] = b"credential";
+pub const PENDING_WITHDRAWAL_SEED: &[u8] = b"pending_withdrawal";
+pub const TREASURY_STATE_SEED: &[u8] = b"treasury_state";
+pub const POOL_STATE_SEED: &[u8] = b"pool_state";
+pub const FEE_VAULT_SEED: &[u8] = b"fee_vault";
+pub const CALL_AUTHORITY_SEED: &[u8] = b"call_authority";
 
 /// Circuit type identifiers
 // Circuit identifiers
 pub const CIRCUIT_SHIELD: u8 = 0;
 pub const CIRCUIT_TRANSFER: u8 = 1;
 pub const CIRCUIT_UNSHIELD: u8 = 2;
+pub const CIRCUIT_BATCH_APPEND: u8 = 3;
+pub const CIRCUIT_DISCLOSURE: u8 = 4;
+
+/// Pause bitmask flags (bit index = circuit identifier)
+pub const PAUSE_SHIELD: u8 = 1 << CIRCUIT_SHIELD;
+pub const PAUSE_TRANSFER: u8 = 1 << CIRCUIT_TRANSFER;
+pub const PAUSE_UNSHIELD: u8 = 1 << CIRCUIT_UNSHIELD;
+pub const PAUSE_ALL: u8 = PAUSE_SHIELD | PAUSE_TRANSFER | PAUSE_UNSHIELD;
 
 /// Number of public inputs per circuit (from ABI.md)
-pub const SHIELD_PUBLIC_INPUTS: usize = 1;
-pub const TRANSFER_PUBLIC_INPUTS: usize = 4;
-pub const UNSHIELD_PUBLIC_INPUTS: usize = 6;
+pub const SHIELD_PUBLIC_INPUTS: usize = 3;
+pub const TRANSFER_PUBLIC_INPUTS: usize = 5;
+pub const UNSHIELD_PUBLIC_INPUTS: usize = 8;
+pub const BATCH_APPEND_PUBLIC_INPUTS: usize = 3 + BATCH_APPEND_SIZE;
+pub const DISCLOSURE_PUBLIC_INPUTS: usize = 3 + DISCLOSURE_SIZE;
+
+/// Leaves appended per batch_append proof (subtree of height 4)
+pub const BATCH_APPEND_SIZE: usize = 16;
+
+/// Tree depth the batch_append circuit is compiled for
+pub const BATCH_APPEND_DEPTH: u8 = 20;
+
+/// Commitments covered by one disclosure proof (zero entries are padding)
+pub const DISCLOSURE_SIZE: usize = 4;
+
+/// Maximum commitments waiting in a tree's queue for batch_append
+pub const COMMITMENT_QUEUE_CAPACITY: usize = 256;
+
+/// Maximum encrypted note payload attached to a shield/transfer output
+/// Bound by the proof through its ciphertext_hash public input
+pub const MAX_ENCRYPTED_OUTPUT_LEN: usize = 256;
 
 /// Maximum verification key size in bytes (conservative estimate)
 pub const MAX_VK_SIZE: usize = 8192;
 
+/// Current on-chain account layout version
+/// Accounts created before versioning was introduced are version 0 and must be
+/// upgraded with the migrate_* instructions
+pub const ACCOUNT_VERSION: u8 = 1;
+
+/// Reserved (zeroed) bytes at the end of each account for future fields
+/// New fields are carved out of this space so account sizes stay stable
+pub const CONFIG_RESERVED_LEN: usize = 5;
+pub const ROOTS_RESERVED_LEN: usize = 52;
+pub const NULLIFIERS_RESERVED_LEN: usize = 64;
+pub const VK_RESERVED_LEN: usize = 64;
+pub const MULTISIG_RESERVED_LEN: usize = 64;
+pub const PROPOSAL_RESERVED_LEN: usize = 32;
+pub const ATTESTERS_RESERVED_LEN: usize = 64;
+pub const PENDING_ROOT_RESERVED_LEN: usize = 28;
+pub const QUEUE_RESERVED_LEN: usize = 64;
+pub const ASSOCIATION_RESERVED_LEN: usize = 64;
+pub const DENY_LIST_RESERVED_LEN: usize = 64;
+pub const CREDENTIAL_RESERVED_LEN: usize = 32;
+pub const PENDING_WITHDRAWAL_RESERVED_LEN: usize = 32;
+pub const TREASURY_STATE_RESERVED_LEN: usize = 64;
+pub const POOL_STATE_RESERVED_LEN: usize = 56;
+pub const FEE_VAULT_RESERVED_LEN: usize = 64;
+
 /// Domain separator for ABI hash computation
 pub const ABI_VERSION: u8 = 2;
 
@@ -44,6 +110,30 @@ pub const NULLIFIER_SHARD_SIZE: usize = 10000;
 /// Maximum nullifier capacity before requiring new shard (safety limit)
 pub const MAX_NULLIFIERS_PER_SHARD: usize = 100000;
 
+/// Maximum number of signers in the built-in admin multisig
+pub const MAX_MULTISIG_SIGNERS: usize = 10;
+
+/// Maximum number of accounts a multisig proposal may reference
+pub const MAX_PROPOSAL_ACCOUNTS: usize = 16;
+
+/// Maximum instruction data length a multisig proposal may carry
+pub const MAX_PROPOSAL_DATA_LEN: usize = 1024;
+
+/// Maximum number of registered root attesters
+pub const MAX_ATTESTERS: usize = 16;
+
+/// Recent association set roots accepted by unshield
+pub const ASSOCIATION_ROOT_WINDOW: usize = 32;
+
+/// Maximum number of recipients on the unshield deny-list
+pub const MAX_DENY_LIST_ENTRIES: usize = 256;
+
+/// Protocol fees are quoted in basis points of the moved amount
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Upper bound on the shield and unshield protocol fee (10%)
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 1_000;
+
 /// Poseidon domain separation tags (circuit-side constants)
 /// These should match the circuit implementation
 pub const POSEIDON_COMMIT_TAG: &str = "NoirWire-Commitment-v1";
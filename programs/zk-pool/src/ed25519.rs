@@ -0,0 +1,197 @@
+use crate::errors::ZkPoolError;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+// ============================================================================
+// ED25519 SIGNATURE CHECKS
+// ============================================================================
+//
+// Programs cannot verify Ed25519 signatures directly. Instead the transaction
+// carries an instruction for the native Ed25519 program, which the runtime
+// verifies before our instruction runs. We load that instruction from the
+// instructions sysvar and check that it covers the expected signer and message.
+//
+// Ed25519 instruction data layout:
+//   [0]      number of signatures
+//   [1]      padding
+//   [2..16]  offsets (7 x u16 LE) for the first signature
+//   [..]     signature / public key / message bytes
+//
+// ============================================================================
+
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+const PUBKEY_LEN: usize = 32;
+const SIGNATURE_LEN: usize = 64;
+
+/// Instruction index value meaning "this instruction's own data"
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Message signed by the root signer:
+/// program_id || pool || tree_index (LE) || root || sequence (LE)
+pub fn signed_root_message(
+    program_id: &Pubkey,
+    pool: &Pubkey,
+    tree_index: u32,
+    root: &[u8; 32],
+    sequence: u64,
+) -> [u8; 108] {
+    let mut message = [0u8; 108];
+    message[..32].copy_from_slice(program_id.as_ref());
+    message[32..64].copy_from_slice(pool.as_ref());
+    message[64..68].copy_from_slice(&tree_index.to_le_bytes());
+    message[68..100].copy_from_slice(root);
+    message[100..].copy_from_slice(&sequence.to_le_bytes());
+    message
+}
+
+/// Check that the instruction right before the current one is an Ed25519
+/// verification of `message` signed by `signer`
+pub fn verify_preceding_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current = load_current_index_checked(instructions_sysvar)?;
+    require!(current > 0, ZkPoolError::InvalidRootSignature);
+
+    let ix = load_instruction_at_checked((current - 1) as usize, instructions_sysvar)?;
+    verify_ed25519_instruction(&ix, signer, message)
+}
+
+/// Check that an Ed25519 program instruction covers exactly one signature by
+/// `signer` over `message`, with all data inline
+pub fn verify_ed25519_instruction(ix: &Instruction, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    require_keys_eq!(
+        ix.program_id,
+        ed25519_program::ID,
+        ZkPoolError::InvalidRootSignature
+    );
+    require!(ix.accounts.is_empty(), ZkPoolError::InvalidRootSignature);
+
+    let data = &ix.data;
+    require!(
+        data.len() >= SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN && data[0] == 1,
+        ZkPoolError::InvalidRootSignature
+    );
+
+    let offset = |i: usize| {
+        let at = SIGNATURE_OFFSETS_START + i * 2;
+        u16::from_le_bytes([data[at], data[at + 1]])
+    };
+    let signature_offset = offset(0) as usize;
+    let signature_ix = offset(1);
+    let pubkey_offset = offset(2) as usize;
+    let pubkey_ix = offset(3);
+    let message_offset = offset(4) as usize;
+    let message_size = offset(5) as usize;
+    let message_ix = offset(6);
+
+    // Signature, key and message must live in the Ed25519 instruction itself,
+    // otherwise the verified bytes could come from an unrelated instruction
+    require!(
+        signature_ix == CURRENT_INSTRUCTION
+            && pubkey_ix == CURRENT_INSTRUCTION
+            && message_ix == CURRENT_INSTRUCTION,
+        ZkPoolError::InvalidRootSignature
+    );
+
+    require!(
+        signature_offset + SIGNATURE_LEN <= data.len(),
+        ZkPoolError::InvalidRootSignature
+    );
+
+    let pubkey = data
+        .get(pubkey_offset..pubkey_offset + PUBKEY_LEN)
+        .ok_or(ZkPoolError::InvalidRootSignature)?;
+    require!(pubkey == signer.as_ref(), ZkPoolError::InvalidRootSignature);
+
+    let signed = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ZkPoolError::InvalidRootSignature)?;
+    require!(signed == message, ZkPoolError::InvalidRootSignature);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build Ed25519 instruction data the way the client helper lays it out
+    fn ed25519_data(pubkey: &Pubkey, message: &[u8], ix_index: u16) -> Vec<u8> {
+        let pubkey_offset = SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN;
+        let signature_offset = pubkey_offset + PUBKEY_LEN;
+        let message_offset = signature_offset + SIGNATURE_LEN;
+
+        let mut data = vec![1u8, 0];
+        for value in [
+            signature_offset as u16,
+            ix_index,
+            pubkey_offset as u16,
+            ix_index,
+            message_offset as u16,
+            message.len() as u16,
+            ix_index,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(pubkey.as_ref());
+        data.extend_from_slice(&[0u8; SIGNATURE_LEN]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    fn ed25519_ix(data: Vec<u8>) -> Instruction {
+        Instruction {
+            program_id: ed25519_program::ID,
+            accounts: vec![],
+            data,
+        }
+    }
+
+    #[test]
+    fn test_ed25519_instruction_matches_signer_and_message() {
+        let signer = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+        let message = signed_root_message(&crate::ID, &pool, 0, &[5u8; 32], 9);
+        let ix = ed25519_ix(ed25519_data(&signer, &message, CURRENT_INSTRUCTION));
+
+        assert!(verify_ed25519_instruction(&ix, &signer, &message).is_ok());
+
+        // Wrong signer
+        assert!(verify_ed25519_instruction(&ix, &Pubkey::new_unique(), &message).is_err());
+
+        // Different sequence number
+        let replay = signed_root_message(&crate::ID, &pool, 0, &[5u8; 32], 10);
+        assert!(verify_ed25519_instruction(&ix, &signer, &replay).is_err());
+
+        // Same root and sequence for another tree
+        let other_tree = signed_root_message(&crate::ID, &pool, 1, &[5u8; 32], 9);
+        assert!(verify_ed25519_instruction(&ix, &signer, &other_tree).is_err());
+    }
+
+    #[test]
+    fn test_ed25519_instruction_rejects_external_data() {
+        let signer = Pubkey::new_unique();
+        let message = [1u8; 108];
+
+        // Data referenced from another instruction
+        let ix = ed25519_ix(ed25519_data(&signer, &message, 0));
+        assert!(verify_ed25519_instruction(&ix, &signer, &message).is_err());
+
+        // Not the Ed25519 program
+        let mut ix = ed25519_ix(ed25519_data(&signer, &message, CURRENT_INSTRUCTION));
+        ix.program_id = Pubkey::new_unique();
+        assert!(verify_ed25519_instruction(&ix, &signer, &message).is_err());
+
+        // Truncated data
+        let mut data = ed25519_data(&signer, &message, CURRENT_INSTRUCTION);
+        data.truncate(data.len() - 1);
+        assert!(verify_ed25519_instruction(&ed25519_ix(data), &signer, &message).is_err());
+    }
+}
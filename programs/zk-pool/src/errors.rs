@@ -8,7 +8,7 @@ pub enum ZkPoolError {
     #[msg("Invalid root window size: must be between 1 and MAX_ROOT_WINDOW")]
     InvalidRootWindow,
 
-    #[msg("Invalid circuit type: must be 0 (shield), 1 (transfer), or 2 (unshield)")]
+    #[msg("Invalid circuit type: must be 0 (shield), 1 (transfer), 2 (unshield), 3 (batch_append) or 4 (disclosure)")]
     InvalidCircuitType,
 
     #[msg("Verification key hash mismatch")]
@@ -70,4 +70,186 @@ pub enum ZkPoolError {
 
     #[msg("Invalid encoding: field element or coordinate out of BN254 range")]
     InvalidEncoding,
+
+    #[msg(
+        "Invalid multisig signer set: must be non-empty, unique and at most MAX_MULTISIG_SIGNERS"
+    )]
+    InvalidMultisigSigners,
+
+    #[msg("Invalid multisig threshold: must be between 1 and the number of signers")]
+    InvalidMultisigThreshold,
+
+    #[msg("Signer is not a member of the multisig")]
+    NotMultisigSigner,
+
+    #[msg("Proposal already approved by this signer")]
+    ProposalAlreadyApproved,
+
+    #[msg("Proposal has not reached the approval threshold")]
+    ProposalThresholdNotMet,
+
+    #[msg("Proposal already executed")]
+    ProposalAlreadyExecuted,
+
+    #[msg("Invalid proposal: too many accounts or instruction data too large")]
+    InvalidProposal,
+
+    #[msg("Accounts passed to execute do not match the proposal")]
+    ProposalAccountMismatch,
+
+    #[msg("Invalid circuit mask: must be a non-empty combination of PAUSE_* flags")]
+    InvalidCircuitMask,
+
+    #[msg("Account is not in a supported legacy layout (already migrated?)")]
+    InvalidAccountVersion,
+
+    #[msg("Invalid pause duration: must not be negative")]
+    InvalidPauseDuration,
+
+    #[msg("Roots account is not initialized")]
+    RootsNotInitialized,
+
+    #[msg("Zero root is not a valid Merkle root")]
+    ZeroRoot,
+
+    #[msg("Root equals the most recently added root")]
+    DuplicateRoot,
+
+    #[msg("Root is already in the root window")]
+    RootAlreadyInWindow,
+
+    #[msg("Attestation mode is enabled: roots must be added through attest_root")]
+    AttestationRequired,
+
+    #[msg("Attestation mode is disabled")]
+    AttestationDisabled,
+
+    #[msg("Invalid attesters: empty, duplicated or above MAX_ATTESTERS")]
+    InvalidAttesters,
+
+    #[msg("Invalid attestation threshold or expiry")]
+    InvalidAttestationThreshold,
+
+    #[msg("Signer is not a registered attester")]
+    NotAttester,
+
+    #[msg("Attester already attested this root")]
+    AlreadyAttested,
+
+    #[msg("Pending attestation has not expired")]
+    PendingRootNotExpired,
+
+    #[msg("Root signer is not configured")]
+    RootSignerNotSet,
+
+    #[msg("Missing or invalid Ed25519 signature for root")]
+    InvalidRootSignature,
+
+    #[msg("Root sequence number does not match the next expected sequence")]
+    InvalidRootSequence,
+
+    #[msg("Commitment tree is full: call rollover_tree")]
+    TreeFull,
+
+    #[msg("Commitment tree is not full yet")]
+    TreeNotFull,
+
+    #[msg("Root belongs to a different commitment tree")]
+    InvalidTreeIndex,
+
+    #[msg("Commitment queue is full: submit batch_append first")]
+    QueueFull,
+
+    #[msg("Not enough queued commitments for a batch")]
+    BatchNotReady,
+
+    #[msg("Encrypted output exceeds MAX_ENCRYPTED_OUTPUT_LEN")]
+    EncryptedOutputTooLarge,
+
+    #[msg("Encrypted output does not match the proof's ciphertext_hash")]
+    CiphertextHashMismatch,
+
+    #[msg("Disclosure must list at least one commitment and no duplicates")]
+    InvalidDisclosure,
+
+    #[msg("Association root not found in the approved association set window")]
+    AssociationRootNotFound,
+
+    #[msg("Recipient is on the deny-list")]
+    RecipientDenied,
+
+    #[msg("Deny-list is full")]
+    DenyListFull,
+
+    #[msg("Depositor credential missing, expired or from a different issuer")]
+    CredentialRequired,
+
+    #[msg("Credential issuer not set")]
+    CredentialIssuerNotSet,
+
+    #[msg("Credential expiry must be in the future")]
+    InvalidCredentialExpiry,
+
+    #[msg("Shield amount exceeds max_shield_amount")]
+    ShieldLimitExceeded,
+
+    #[msg("Unshield amount exceeds max_unshield_amount")]
+    UnshieldLimitExceeded,
+
+    #[msg("Deposit would exceed the pool TVL cap")]
+    TvlCapExceeded,
+
+    #[msg("Unshield amount exceeds the delay threshold, use submit_unshield_delayed")]
+    WithdrawalDelayRequired,
+
+    #[msg("Withdrawal delay has not elapsed")]
+    WithdrawalNotClaimable,
+
+    #[msg("Treasury outflow limit reached, retry later")]
+    WithdrawalRateLimited,
+
+    #[msg("Rate limit window must be non-zero when a limit is set")]
+    InvalidRateLimit,
+
+    #[msg("Treasury balance below expected balance plus rent-exempt minimum")]
+    TreasuryInsolvent,
+
+    #[msg("Payout would leave the treasury below its rent-exempt minimum")]
+    TreasuryRentExemption,
+
+    #[msg("Payout would leave a new recipient below the rent-exempt minimum")]
+    RecipientBelowRentMinimum,
+
+    #[msg("Protocol fee exceeds MAX_PROTOCOL_FEE_BPS")]
+    InvalidProtocolFee,
+
+    #[msg("Fee vault account required to collect the protocol fee")]
+    FeeVaultRequired,
+
+    #[msg("Fee vault holds less than the requested amount")]
+    InsufficientProtocolFees,
+
+    #[msg("Amount does not match the pool denomination")]
+    InvalidDenomination,
+
+    #[msg("Denomination can only be set while the pool holds no tracked deposits")]
+    PoolNotEmpty,
+
+    #[msg("Call hash does not match the proof's public input")]
+    CallHashMismatch,
+
+    #[msg("Call target must be an executable program other than this one")]
+    InvalidCallTarget,
+
+    #[msg("Root window grows too much for one realloc, raise it in smaller steps")]
+    RootWindowGrowthTooLarge,
+
+    #[msg("Legacy root or leaf count does not match the migrated tree")]
+    LegacyTreeMismatch,
+
+    #[msg("Root signer is set: roots must be added through add_signed_root")]
+    SignedRootRequired,
+
+    #[msg("Call left part of the payout in the call authority")]
+    CallPayoutNotSpent,
 }
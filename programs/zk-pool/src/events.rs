@@ -10,6 +10,14 @@ pub struct Initialized {
     pub timestamp: i64,
 }
 
+/// Emitted when solvency tracking starts for a pool
+#[event]
+pub struct PoolStateInitialized {
+    pub expected_balance: u64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
 /// Emitted when a verification key is set or updated
 #[event]
 pub struct VerificationKeySet {
@@ -21,8 +29,119 @@ pub struct VerificationKeySet {
 /// Emitted when a new Merkle root is added
 #[event]
 pub struct RootAdded {
+    pub tree_index: u32,
     pub root: [u8; 32],
     pub index: u16,
+    pub seq: u64, // gap-free; indexers can detect missed roots
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the attester set or attestation mode changes
+#[event]
+pub struct AttestersUpdated {
+    pub attesters: Vec<Pubkey>,
+    pub threshold: u8,
+    pub expiry_slots: u64,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when an attester attests a root
+#[event]
+pub struct RootAttested {
+    pub root: [u8; 32],
+    pub attester: Pubkey,
+    pub attestations: u8,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when a batch of queued commitments is appended to a tree
+#[event]
+pub struct BatchAppended {
+    pub tree_index: u32,
+    pub start_index: u64,
+    pub count: u16,
+    pub old_root: [u8; 32],
+    pub new_root: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Emitted when a disclosure proof verifies; records the statement for auditors
+#[event]
+pub struct DisclosureVerified {
+    pub tree_index: u32,
+    pub root: [u8; 32],
+    pub viewing_key: [u8; 32],
+    pub total: u64,
+    pub commitments: Vec<[u8; 32]>, // Disclosed notes, padding removed
+    pub requester: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a new commitment tree becomes active
+#[event]
+pub struct TreeRolledOver {
+    pub previous_tree: u32,
+    pub tree_index: u32,
+    pub previous_leaf_count: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the off-chain root signer changes
+#[event]
+pub struct RootSignerSet {
+    pub root_signer: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a stored root is revoked
+#[event]
+pub struct RootRevoked {
+    pub root: [u8; 32],
+    pub seq: u64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the root window (RootsAccount capacity) is resized
+#[event]
+pub struct RootWindowChanged {
+    pub tree_index: u32,
+    pub old_window: u16,
+    pub new_window: u16,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the maximum root age changes
+#[event]
+pub struct MaxRootAgeSet {
+    pub max_root_age_slots: u64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when deposit and withdrawal limits change
+#[event]
+pub struct LimitsSet {
+    pub max_shield_amount: u64,
+    pub max_unshield_amount: u64,
+    pub tvl_cap: u64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when funds are deposited into the pool
+#[event]
+pub struct Shielded {
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub protocol_fee: u64, // Charged on top of amount
+    pub commitment: [u8; 32],
     pub timestamp: i64,
 }
 
@@ -30,7 +149,10 @@ pub struct RootAdded {
 #[event]
 pub struct NewCommitment {
     pub commitment: [u8; 32],
-    pub circuit: u8, // 0=shield, 1=transfer, 2=unshield (for indexing)
+    pub tree_index: u32,
+    pub leaf_index: u64,
+    pub circuit: u8,               // 0=shield, 1=transfer, 2=unshield (for indexing)
+    pub encrypted_output: Vec<u8>, // Note ciphertext for recipient trial-decryption
     pub timestamp: i64,
 }
 
@@ -42,6 +164,81 @@ pub struct NullifierSpent {
     pub timestamp: i64,
 }
 
+/// Emitted when the treasury outflow limit changes
+#[event]
+pub struct WithdrawalRateLimitSet {
+    pub max_outflow: u64,
+    pub window_slots: u64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when fixed-denomination mode is enabled, changed or disabled
+#[event]
+pub struct DenominationSet {
+    pub denomination: u64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the protocol fee schedule or fee manager changes
+#[event]
+pub struct ProtocolFeesSet {
+    pub shield_fee_bps: u16,
+    pub unshield_fee_bps: u16,
+    pub fee_manager: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the fee manager withdraws collected protocol fees
+#[event]
+pub struct ProtocolFeesWithdrawn {
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub fee_manager: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when topping up new recipients from the fee is toggled
+#[event]
+pub struct RecipientTopUpSet {
+    pub enabled: bool,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the withdrawal delay settings change
+#[event]
+pub struct WithdrawalDelaySet {
+    pub threshold: u64,
+    pub delay_slots: u64,
+    pub guardian: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when an unshield is queued behind the withdrawal delay
+#[event]
+pub struct WithdrawalQueued {
+    pub nullifier: [u8; 32],
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub claimable_slot: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a queued withdrawal is cancelled; its nullifier stays spent
+#[event]
+pub struct WithdrawalCancelled {
+    pub nullifier: [u8; 32],
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
 /// Emitted when funds are unshielded to a public recipient
 #[event]
 pub struct Unshielded {
@@ -49,6 +246,78 @@ pub struct Unshielded {
     pub amount: u64,
     pub fee: u64,
     pub nullifier: [u8; 32],
+    pub association_root: [u8; 32], // Zero when the user opted out
+    pub top_up: u64,                // Part of the fee paid to the recipient for rent
+    pub protocol_fee: u64,          // Sent to the fee vault
+    pub timestamp: i64,
+}
+
+/// Emitted after submit_unshield_and_call invokes its target program
+#[event]
+pub struct UnshieldCallExecuted {
+    pub nullifier: [u8; 32],
+    pub target_program: Pubkey,
+    pub call_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Emitted when the association set curator changes
+#[event]
+pub struct AssociationCuratorSet {
+    pub curator: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when an association set root is approved
+#[event]
+pub struct AssociationRootAdded {
+    pub root: [u8; 32],
+    pub index: u16,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the deny-list compliance key changes
+#[event]
+pub struct ComplianceAuthoritySet {
+    pub compliance: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a recipient is added to or removed from the deny-list
+#[event]
+pub struct DenyListUpdated {
+    pub recipient: Pubkey,
+    pub denied: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the deposit credential policy changes
+#[event]
+pub struct CredentialPolicySet {
+    pub required: bool,
+    pub issuer: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a depositor credential is issued or renewed
+#[event]
+pub struct CredentialIssued {
+    pub holder: Pubkey,
+    pub issuer: Pubkey,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a depositor credential is revoked
+#[event]
+pub struct CredentialRevoked {
+    pub holder: Pubkey,
+    pub authority: Pubkey,
     pub timestamp: i64,
 }
 
@@ -56,6 +325,69 @@ pub struct Unshielded {
 #[event]
 pub struct PoolPausedChanged {
     pub paused: bool,
+    pub circuit_mask: u8, // PAUSE_* bits whose state actually changed
+    pub paused_mask: u8,  // resulting pause bitmask
     pub admin: Pubkey,
     pub timestamp: i64,
 }
+
+/// Emitted when the maximum pause duration changes
+#[event]
+pub struct MaxPauseDurationSet {
+    pub max_pause_duration: i64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when an account is upgraded to a newer layout version
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when the built-in admin multisig is created
+#[event]
+pub struct MultisigCreated {
+    pub multisig: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when the multisig signer set or threshold changes
+#[event]
+pub struct MultisigSignersChanged {
+    pub multisig: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when a multisig proposal is created
+#[event]
+pub struct ProposalCreated {
+    pub proposal: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a multisig signer approves a proposal
+#[event]
+pub struct ProposalApproved {
+    pub proposal: Pubkey,
+    pub signer: Pubkey,
+    pub approvals: u8,
+    pub timestamp: i64,
+}
+
+/// Emitted when a multisig proposal is executed
+#[event]
+pub struct ProposalExecuted {
+    pub proposal: Pubkey,
+    pub index: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,45 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::AssociationRootAdded;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AddAssociationRoot<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        seeds = [ASSOCIATION_ROOTS_SEED],
+        bump = association_roots.bump
+    )]
+    pub association_roots: Account<'info, AssociationRoots>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn add_association_root(ctx: Context<AddAssociationRoot>, root: [u8; 32]) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let association_roots = &mut ctx.accounts.association_roots;
+
+    require!(
+        association_roots.is_manager(&authority, &ctx.accounts.config.admin),
+        ZkPoolError::Unauthorized
+    );
+
+    // Rejects zero roots and roots already in the window
+    let index = association_roots.add_root(root)?;
+
+    emit!(AssociationRootAdded {
+        root,
+        index,
+        authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
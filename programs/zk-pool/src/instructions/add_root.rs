@@ -15,34 +15,39 @@ pub struct AddRoot<'info> {
 
     #[account(
         mut,
-        seeds = [ROOTS_SEED],
-        bump
+        seeds = [ROOTS_SEED, tree_seed(config.active_tree).as_slice()],
+        bump = roots.bump
     )]
     pub roots: Account<'info, RootsAccount>,
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
-
-    /// CHECK: Admin account is validated through has_one constraint on config
-    pub admin: UncheckedAccount<'info>,
-
-    pub system_program: Program<'info, System>,
+    pub admin: Signer<'info>,
 }
 
 pub fn add_root(ctx: Context<AddRoot>, root: [u8; 32]) -> Result<()> {
-    let roots = &mut ctx.accounts.roots;
+    let config = &ctx.accounts.config;
 
-    // Ensure roots account is initialized (via initialize instruction)
-    require!(roots.capacity > 0, ZkPoolError::VkNotSet);
+    // In attestation mode roots only enter through the attester quorum,
+    // and with a root signer set only through add_signed_root
+    require!(!config.root_attestation, ZkPoolError::AttestationRequired);
+    require!(
+        config.root_signer == Pubkey::default(),
+        ZkPoolError::SignedRootRequired
+    );
+
+    let roots = &mut ctx.accounts.roots;
 
-    // Add root to ring buffer
+    // Add root to ring buffer (rejects uninitialized buffer, zero and duplicate roots)
+    let clock = Clock::get()?;
     let index = roots.cursor;
-    roots.add_root(root);
+    let seq = roots.add_root(root, clock.slot, clock.unix_timestamp)?;
 
     emit!(RootAdded {
+        tree_index: roots.tree_index,
         root,
         index,
-        timestamp: Clock::get()?.unix_timestamp,
+        seq,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
     });
 
     Ok(())
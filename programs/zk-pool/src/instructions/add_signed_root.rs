@@ -0,0 +1,65 @@
+use crate::constants::*;
+use crate::ed25519;
+use crate::errors::ZkPoolError;
+use crate::events::RootAdded;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+
+#[derive(Accounts)]
+pub struct AddSignedRoot<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        seeds = [ROOTS_SEED, tree_seed(roots.tree_index).as_slice()],
+        bump = roots.bump
+    )]
+    pub roots: Account<'info, RootsAccount>,
+
+    /// CHECK: Instructions sysvar, address checked
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+/// The Ed25519 sigverify instruction must immediately precede this one.
+/// Anyone may relay; the signature binds program, pool, root and sequence.
+pub fn add_signed_root(ctx: Context<AddSignedRoot>, root: [u8; 32], sequence: u64) -> Result<()> {
+    let config = &ctx.accounts.config;
+    require!(!config.root_attestation, ZkPoolError::AttestationRequired);
+    require!(
+        config.root_signer != Pubkey::default(),
+        ZkPoolError::RootSignerNotSet
+    );
+
+    // Sequence must be the next one so a signed root cannot be replayed
+    let roots = &mut ctx.accounts.roots;
+    require!(sequence == roots.next_seq, ZkPoolError::InvalidRootSequence);
+
+    let message =
+        ed25519::signed_root_message(&crate::ID, &config.key(), roots.tree_index, &root, sequence);
+    ed25519::verify_preceding_signature(
+        &ctx.accounts.instructions.to_account_info(),
+        &config.root_signer,
+        &message,
+    )?;
+
+    let clock = Clock::get()?;
+    let index = roots.cursor;
+    let seq = roots.add_root(root, clock.slot, clock.unix_timestamp)?;
+
+    emit!(RootAdded {
+        tree_index: roots.tree_index,
+        root,
+        index,
+        seq,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use crate::constants::*;
+use crate::events::ProposalApproved;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    #[account(
+        seeds = [MULTISIG_SEED],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.index.to_le_bytes()],
+        bump = proposal.bump,
+        has_one = multisig
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub signer: Signer<'info>,
+}
+
+pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let proposal = &mut ctx.accounts.proposal;
+    let signer = ctx.accounts.signer.key();
+
+    proposal.approve(multisig, signer)?;
+
+    emit!(ProposalApproved {
+        proposal: proposal.key(),
+        signer,
+        approvals: proposal.approvals.len() as u8,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::{RootAdded, RootAttested};
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(root: [u8; 32])]
+pub struct AttestRoot<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        seeds = [ATTESTERS_SEED],
+        bump = attester_set.bump
+    )]
+    pub attester_set: Account<'info, AttesterSet>,
+
+    #[account(
+        mut,
+        seeds = [ROOTS_SEED, tree_seed(roots.tree_index).as_slice()],
+        bump = roots.bump
+    )]
+    pub roots: Account<'info, RootsAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = attester,
+        space = PendingRoot::LEN,
+        seeds = [PENDING_ROOT_SEED, root.as_ref()],
+        bump
+    )]
+    pub pending_root: Account<'info, PendingRoot>,
+
+    /// Receives the pending account's rent once the root is accepted
+    /// CHECK: Must match pending_root.payer (validated in handler)
+    #[account(mut)]
+    pub rent_recipient: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub attester: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn attest_root(ctx: Context<AttestRoot>, root: [u8; 32]) -> Result<()> {
+    require!(
+        ctx.accounts.config.root_attestation,
+        ZkPoolError::AttestationDisabled
+    );
+
+    let attester_set = &ctx.accounts.attester_set;
+    let attester = ctx.accounts.attester.key();
+    require!(
+        attester_set.is_attester(&attester),
+        ZkPoolError::NotAttester
+    );
+
+    let clock = Clock::get()?;
+    let tree_index = ctx.accounts.roots.tree_index;
+    let pending = &mut ctx.accounts.pending_root;
+
+    // Freshly created account: start the first round
+    if pending.payer == Pubkey::default() {
+        pending.version = ACCOUNT_VERSION;
+        pending.root = root;
+        pending.attesters = Vec::new();
+        pending.first_slot = clock.slot;
+        pending.payer = attester;
+        pending.bump = ctx.bumps.pending_root;
+        pending.tree_index = tree_index;
+    }
+
+    // Expired round: discard stale attestations and start over
+    if pending.is_expired(clock.slot, attester_set.expiry_slots) {
+        pending.attesters.clear();
+        pending.first_slot = clock.slot;
+        pending.tree_index = tree_index;
+    }
+
+    // All attestations in a round must target the same tree
+    require!(
+        pending.tree_index == tree_index,
+        ZkPoolError::InvalidTreeIndex
+    );
+
+    require!(
+        !pending.attesters.contains(&attester),
+        ZkPoolError::AlreadyAttested
+    );
+
+    // Drop attesters removed from the set so the list stays within MAX_ATTESTERS
+    pending.attesters.retain(|a| attester_set.is_attester(a));
+    pending.attesters.push(attester);
+
+    let attestations = pending.attestation_count(attester_set);
+
+    emit!(RootAttested {
+        root,
+        attester,
+        attestations: attestations as u8,
+        threshold: attester_set.threshold,
+        timestamp: clock.unix_timestamp,
+    });
+
+    if attestations < attester_set.threshold as usize {
+        return Ok(());
+    }
+
+    // Quorum reached: accept the root and close the pending account
+    let roots = &mut ctx.accounts.roots;
+    let index = roots.cursor;
+    let seq = roots.add_root(root, clock.slot, clock.unix_timestamp)?;
+
+    emit!(RootAdded {
+        tree_index: roots.tree_index,
+        root,
+        index,
+        seq,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    require!(
+        ctx.accounts.rent_recipient.key() == pending.payer,
+        ZkPoolError::Unauthorized
+    );
+    pending.close(ctx.accounts.rent_recipient.to_account_info())?;
+
+    Ok(())
+}
@@ -0,0 +1,189 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::{BatchAppended, RootAdded};
+use crate::state::*;
+use crate::verifier::verify_proof;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct BatchAppend<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        seeds = [VK_SEED, &[CIRCUIT_BATCH_APPEND]],
+        bump = vk_account.bump
+    )]
+    pub vk_account: Account<'info, VerificationKeyAccount>,
+
+    #[account(
+        mut,
+        seeds = [COMMITMENT_QUEUE_SEED, &queue.tree_index.to_le_bytes()],
+        bump = queue.bump
+    )]
+    pub queue: Account<'info, CommitmentQueue>,
+
+    /// Roots of the queue's tree, receives new_root
+    #[account(
+        mut,
+        seeds = [ROOTS_SEED, tree_seed(queue.tree_index).as_slice()],
+        bump = roots.bump
+    )]
+    pub roots: Account<'info, RootsAccount>,
+}
+
+/// Permissionless: the proof alone authorizes the root update
+pub fn batch_append(ctx: Context<BatchAppend>, proof: Vec<u8>, new_root: [u8; 32]) -> Result<()> {
+    let config = &ctx.accounts.config;
+
+    // Verify VK is set (not zero hash) and matches config
+    require!(
+        config.batch_append_vk_hash != [0u8; 32],
+        ZkPoolError::VkNotSet
+    );
+    require!(
+        ctx.accounts.vk_account.vk_hash == config.batch_append_vk_hash,
+        ZkPoolError::VkHashMismatch
+    );
+
+    let queue = &mut ctx.accounts.queue;
+    let old_root = queue.current_root;
+    let start_index = queue.next_index;
+    let (public_inputs, count) =
+        batch_public_inputs(queue, config.merkle_depth, config.active_tree, new_root)?;
+
+    verify_proof(
+        &ctx.accounts.vk_account,
+        &proof,
+        &public_inputs,
+        &config.abi_hash,
+    )?;
+
+    queue.pop_batch(count, new_root);
+
+    let clock = Clock::get()?;
+    let roots = &mut ctx.accounts.roots;
+    let index = roots.cursor;
+    let seq = roots.add_root(new_root, clock.slot, clock.unix_timestamp)?;
+
+    emit!(RootAdded {
+        tree_index: roots.tree_index,
+        root: new_root,
+        index,
+        seq,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(BatchAppended {
+        tree_index: queue.tree_index,
+        start_index,
+        count: count as u16,
+        old_root,
+        new_root,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Public inputs for appending the queue's next batch, and its number of real leaves
+pub(crate) fn batch_public_inputs(
+    queue: &CommitmentQueue,
+    merkle_depth: u8,
+    active_tree: u32,
+    new_root: [u8; 32],
+) -> Result<(Vec<[u8; 32]>, usize)> {
+    // The circuit proves roots of a depth-20 tree only
+    require!(
+        merkle_depth == BATCH_APPEND_DEPTH,
+        ZkPoolError::InvalidMerkleDepth
+    );
+
+    // A retired tree never receives another leaf, so its last batch is zero-padded
+    let allow_partial = queue.tree_index != active_tree;
+    let (leaves, count) = queue.next_batch(allow_partial)?;
+
+    // Public inputs per ABI.md ordering: old_root, new_root, start_index, leaves
+    let mut start_index_field = [0u8; 32];
+    start_index_field[..8].copy_from_slice(&queue.next_index.to_le_bytes());
+
+    let mut public_inputs = Vec::with_capacity(BATCH_APPEND_PUBLIC_INPUTS);
+    public_inputs.push(queue.current_root);
+    public_inputs.push(new_root);
+    public_inputs.push(start_index_field);
+    public_inputs.extend_from_slice(&leaves);
+
+    Ok((public_inputs, count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue_with(count: u8) -> CommitmentQueue {
+        let mut queue = CommitmentQueue {
+            version: ACCOUNT_VERSION,
+            tree_index: 0,
+            current_root: [0u8; 32],
+            next_index: 0,
+            leaves: Vec::new(),
+            bump: 255,
+            reserved: [0u8; QUEUE_RESERVED_LEN],
+        };
+        for i in 1..=count {
+            queue.push([i; 32]).unwrap();
+        }
+        queue
+    }
+
+    fn start_index(field: &[u8; 32]) -> u64 {
+        u64::from_le_bytes(field[..8].try_into().unwrap())
+    }
+
+    #[test]
+    fn test_batch_inputs_advance_across_batches() {
+        let mut queue = queue_with(2 * BATCH_APPEND_SIZE as u8);
+
+        // First batch starts from the empty tree at leaf 0
+        let (inputs, count) =
+            batch_public_inputs(&queue, BATCH_APPEND_DEPTH, 0, [0xaa; 32]).unwrap();
+        assert_eq!(inputs.len(), BATCH_APPEND_PUBLIC_INPUTS);
+        assert_eq!(count, BATCH_APPEND_SIZE);
+        assert_eq!(inputs[0], [0u8; 32]);
+        assert_eq!(inputs[1], [0xaa; 32]);
+        assert_eq!(start_index(&inputs[2]), 0);
+        assert_eq!(inputs[3], [1; 32]);
+        queue.pop_batch(count, [0xaa; 32]);
+
+        // Second batch chains on the first batch's root, one subtree further
+        let (inputs, count) =
+            batch_public_inputs(&queue, BATCH_APPEND_DEPTH, 0, [0xbb; 32]).unwrap();
+        assert_eq!(count, BATCH_APPEND_SIZE);
+        assert_eq!(inputs[0], [0xaa; 32]);
+        assert_eq!(inputs[1], [0xbb; 32]);
+        assert_eq!(start_index(&inputs[2]), BATCH_APPEND_SIZE as u64);
+        assert_eq!(inputs[3], [BATCH_APPEND_SIZE as u8 + 1; 32]);
+        queue.pop_batch(count, [0xbb; 32]);
+
+        assert_eq!(queue.current_root, [0xbb; 32]);
+        assert_eq!(queue.next_index, 2 * BATCH_APPEND_SIZE as u64);
+        assert!(queue.leaves.is_empty());
+    }
+
+    #[test]
+    fn test_batch_inputs_require_circuit_depth() {
+        let queue = queue_with(BATCH_APPEND_SIZE as u8);
+        assert_eq!(
+            batch_public_inputs(&queue, BATCH_APPEND_DEPTH - 1, 0, [0xaa; 32]).unwrap_err(),
+            ZkPoolError::InvalidMerkleDepth.into()
+        );
+        assert_eq!(
+            batch_public_inputs(&queue, 32, 0, [0xaa; 32]).unwrap_err(),
+            ZkPoolError::InvalidMerkleDepth.into()
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::WithdrawalCancelled;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CancelWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        seeds = [PENDING_WITHDRAWAL_SEED, pending_withdrawal.nullifier.as_ref()],
+        bump = pending_withdrawal.bump,
+        close = authority
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// The guardian or the admin may cancel a withdrawal until it is claimed
+///
+/// The nullifier stays spent and the funds stay in the treasury, so the note
+/// is burned; the rent goes to the canceller rather than the submitter.
+pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let authority = ctx.accounts.authority.key();
+    require!(
+        authority == config.admin
+            || (config.guardian != Pubkey::default() && authority == config.guardian),
+        ZkPoolError::Unauthorized
+    );
+
+    // The amount never left the pool
+    let pending = &ctx.accounts.pending_withdrawal;
+    config.tvl = config.tvl.saturating_add(pending.amount);
+
+    emit!(WithdrawalCancelled {
+        nullifier: pending.nullifier,
+        recipient: pending.recipient,
+        amount: pending.amount,
+        authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
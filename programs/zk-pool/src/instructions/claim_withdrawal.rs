@@ -0,0 +1,124 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::Unshielded;
+use crate::instructions::submit_unshield::{check_recipient_allowed, pay_unshield};
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ClaimWithdrawal<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        seeds = [PENDING_WITHDRAWAL_SEED, pending_withdrawal.nullifier.as_ref()],
+        bump = pending_withdrawal.bump,
+        close = payer
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+
+    /// Recipient deny-list, checked again in case the recipient was denied during the delay
+    /// CHECK: Address pinned by seeds, contents read in check_recipient_allowed
+    #[account(
+        seeds = [DENY_LIST_SEED],
+        bump
+    )]
+    pub deny_list: UncheckedAccount<'info>,
+
+    /// Treasury PDA (program-owned, holds pooled SOL/tokens)
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    /// Solvency accounting, checked against the treasury balance once it has been created
+    /// CHECK: Address pinned by seeds, contents read and written in update_pool_state
+    #[account(
+        mut,
+        seeds = [POOL_STATE_SEED],
+        bump
+    )]
+    pub pool_state: UncheckedAccount<'info>,
+
+    /// Protocol fee vault, required while the protocol fee is non-zero
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Option<Account<'info, FeeVault>>,
+
+    /// Treasury outflow limiter; outflow is unlimited until it has been created
+    /// CHECK: Address pinned by seeds, contents read and written in record_treasury_outflow
+    #[account(
+        mut,
+        seeds = [TREASURY_STATE_SEED],
+        bump
+    )]
+    pub treasury_state: UncheckedAccount<'info>,
+
+    /// CHECK: Must match pending_withdrawal.recipient
+    #[account(
+        mut,
+        address = pending_withdrawal.recipient @ ZkPoolError::InvalidRecipient
+    )]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: Rent refund destination, must match pending_withdrawal.payer
+    #[account(
+        mut,
+        address = pending_withdrawal.payer @ ZkPoolError::Unauthorized
+    )]
+    pub payer: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Anyone may claim once the delay has elapsed; the recipient is fixed by the proof
+pub fn claim_withdrawal(ctx: Context<ClaimWithdrawal>) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        !ctx.accounts.config.is_unshield_paused(clock.unix_timestamp),
+        ZkPoolError::PoolPaused
+    );
+
+    let pending = &ctx.accounts.pending_withdrawal;
+    require!(
+        pending.is_claimable(clock.slot),
+        ZkPoolError::WithdrawalNotClaimable
+    );
+
+    check_recipient_allowed(&ctx.accounts.deny_list, &pending.recipient)?;
+
+    let payout = pay_unshield(
+        &ctx.accounts.config,
+        &ctx.accounts.treasury,
+        ctx.bumps.treasury,
+        &ctx.accounts.recipient,
+        &ctx.accounts.treasury_state,
+        ctx.accounts.fee_vault.as_mut(),
+        &ctx.accounts.pool_state,
+        &ctx.accounts.system_program,
+        pending.amount,
+        pending.fee,
+    )?;
+
+    emit!(Unshielded {
+        recipient: pending.recipient,
+        amount: pending.amount,
+        fee: pending.fee,
+        nullifier: pending.nullifier,
+        association_root: pending.association_root,
+        top_up: payout.top_up,
+        protocol_fee: payout.protocol_fee,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ClosePendingRoot<'info> {
+    #[account(
+        seeds = [ATTESTERS_SEED],
+        bump = attester_set.bump
+    )]
+    pub attester_set: Account<'info, AttesterSet>,
+
+    #[account(
+        mut,
+        seeds = [PENDING_ROOT_SEED, pending_root.root.as_ref()],
+        bump = pending_root.bump,
+        close = payer
+    )]
+    pub pending_root: Account<'info, PendingRoot>,
+
+    /// CHECK: Rent refund destination, must match pending_root.payer
+    #[account(
+        mut,
+        address = pending_root.payer @ ZkPoolError::Unauthorized
+    )]
+    pub payer: UncheckedAccount<'info>,
+}
+
+/// Permissionless: anyone may clean up a round that expired without quorum
+pub fn close_pending_root(ctx: Context<ClosePendingRoot>) -> Result<()> {
+    let expiry_slots = ctx.accounts.attester_set.expiry_slots;
+    require!(
+        ctx.accounts
+            .pending_root
+            .is_expired(Clock::get()?.slot, expiry_slots),
+        ZkPoolError::PendingRootNotExpired
+    );
+
+    Ok(())
+}
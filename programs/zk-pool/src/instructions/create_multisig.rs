@@ -0,0 +1,59 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::MultisigCreated;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateMultisig<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ZkPoolError::Unauthorized
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Multisig::LEN,
+        seeds = [MULTISIG_SEED],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_multisig(
+    ctx: Context<CreateMultisig>,
+    signers: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    Multisig::validate_signers(&signers, threshold)?;
+
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.version = ACCOUNT_VERSION;
+    multisig.signers = signers;
+    multisig.threshold = threshold;
+    multisig.proposal_count = 0;
+    multisig.bump = ctx.bumps.multisig;
+
+    // Hand admin rights to the multisig PDA; from here on admin
+    // instructions are only reachable through execute_proposal
+    let config = &mut ctx.accounts.config;
+    config.admin = multisig.key();
+
+    emit!(MultisigCreated {
+        multisig: multisig.key(),
+        signers: multisig.signers.clone(),
+        threshold,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
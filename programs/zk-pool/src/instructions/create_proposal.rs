@@ -0,0 +1,78 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::ProposalCreated;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(accounts: Vec<ProposalAccountMeta>, data: Vec<u8>)]
+pub struct CreateProposal<'info> {
+    #[account(
+        mut,
+        seeds = [MULTISIG_SEED],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::space_for(accounts.len(), data.len()),
+        seeds = [PROPOSAL_SEED, &multisig.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_proposal(
+    ctx: Context<CreateProposal>,
+    accounts: Vec<ProposalAccountMeta>,
+    data: Vec<u8>,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+    let proposer = ctx.accounts.proposer.key();
+
+    require!(
+        multisig.is_signer(&proposer),
+        ZkPoolError::NotMultisigSigner
+    );
+
+    // Bound the stored instruction (must at least carry an Anchor discriminator)
+    require!(
+        accounts.len() <= MAX_PROPOSAL_ACCOUNTS
+            && data.len() >= 8
+            && data.len() <= MAX_PROPOSAL_DATA_LEN,
+        ZkPoolError::InvalidProposal
+    );
+
+    let index = multisig.proposal_count;
+    multisig.proposal_count = index
+        .checked_add(1)
+        .ok_or(ZkPoolError::ArithmeticOverflow)?;
+
+    // Proposer implicitly approves
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.version = ACCOUNT_VERSION;
+    proposal.multisig = multisig.key();
+    proposal.index = index;
+    proposal.proposer = proposer;
+    proposal.accounts = accounts;
+    proposal.data = data;
+    proposal.approvals = vec![proposer];
+    proposal.executed = false;
+    proposal.bump = ctx.bumps.proposal;
+
+    emit!(ProposalCreated {
+        proposal: proposal.key(),
+        index,
+        proposer,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use crate::constants::*;
+use crate::events::ProposalExecuted;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    /// Not `mut`: the proposed instruction may rewrite the multisig itself,
+    /// and a stale copy must not be serialized back over it on exit
+    #[account(
+        seeds = [MULTISIG_SEED],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.index.to_le_bytes()],
+        bump = proposal.bump,
+        has_one = multisig
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: Address is constrained to this program; used as the CPI target
+    #[account(address = crate::ID)]
+    pub zk_pool_program: UncheckedAccount<'info>,
+
+    pub executor: Signer<'info>,
+}
+
+/// Execute an approved proposal
+///
+/// The proposal's accounts must be passed as remaining accounts, in order.
+pub fn execute_proposal<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteProposal<'info>>,
+) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let proposal = &mut ctx.accounts.proposal;
+
+    // Mark executed before the CPI so the proposal cannot be replayed
+    proposal.mark_executed(multisig)?;
+
+    let remaining = ctx.remaining_accounts;
+    let keys: Vec<Pubkey> = remaining.iter().map(|info| info.key()).collect();
+    let metas = proposal.instruction_metas(&keys)?;
+
+    let ix = Instruction {
+        program_id: crate::ID,
+        accounts: metas,
+        data: proposal.data.clone(),
+    };
+
+    let mut infos = remaining.to_vec();
+    infos.push(ctx.accounts.zk_pool_program.to_account_info());
+
+    // The multisig PDA signs as admin of the inner instruction
+    let multisig_seeds = &[MULTISIG_SEED, &[multisig.bump]];
+    invoke_signed(&ix, &infos, &[&multisig_seeds[..]])?;
+
+    emit!(ProposalExecuted {
+        proposal: proposal.key(),
+        index: proposal.index,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
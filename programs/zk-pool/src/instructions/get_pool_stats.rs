@@ -0,0 +1,41 @@
+use crate::constants::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct GetPoolStats<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        seeds = [POOL_STATE_SEED],
+        bump = pool_state.bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    /// Treasury PDA (program-owned, holds pooled SOL/tokens)
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+}
+
+/// Read-only: meant to be simulated, the stats come back as return data
+pub fn get_pool_stats(ctx: Context<GetPoolStats>) -> Result<PoolStats> {
+    let pool_state = &ctx.accounts.pool_state;
+
+    Ok(PoolStats {
+        total_deposited: pool_state.total_deposited,
+        total_withdrawn: pool_state.total_withdrawn,
+        total_fees: pool_state.total_fees,
+        total_protocol_fees: pool_state.total_protocol_fees,
+        expected_balance: pool_state.expected_balance,
+        treasury_balance: ctx.accounts.treasury.lamports(),
+        rent_exempt_minimum: Rent::get()?.minimum_balance(0),
+        tvl: ctx.accounts.config.tvl,
+    })
+}
@@ -0,0 +1,111 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::PoolStateInitialized;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitPoolState<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ZkPoolError::Unauthorized
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = PoolState::LEN,
+        seeds = [POOL_STATE_SEED],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    /// Treasury PDA (program-owned, holds pooled SOL/tokens)
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Start solvency tracking for a pool created before PoolState existed
+///
+/// Totals start at zero and the treasury's current surplus over its
+/// rent-exempt minimum becomes the expected balance.
+pub fn init_pool_state(ctx: Context<InitPoolState>) -> Result<()> {
+    let expected_balance = start_pool_state(
+        &mut ctx.accounts.pool_state,
+        ctx.bumps.pool_state,
+        &ctx.accounts.treasury,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )?;
+
+    emit!(PoolStateInitialized {
+        expected_balance,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Fund the treasury's rent, then start `pool_state` with zero totals and the
+/// treasury's surplus as the expected balance, which is returned
+pub(crate) fn start_pool_state<'info>(
+    pool_state: &mut PoolState,
+    bump: u8,
+    treasury: &SystemAccount<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<u64> {
+    let rent_exempt_minimum = fund_treasury_rent(treasury, payer, system_program)?;
+    let expected_balance = treasury.lamports().saturating_sub(rent_exempt_minimum);
+
+    pool_state.version = ACCOUNT_VERSION;
+    pool_state.total_deposited = 0;
+    pool_state.total_withdrawn = 0;
+    pool_state.total_fees = 0;
+    pool_state.expected_balance = expected_balance;
+    pool_state.bump = bump;
+    pool_state.total_protocol_fees = 0;
+    pool_state.reserved = [0u8; POOL_STATE_RESERVED_LEN];
+
+    Ok(expected_balance)
+}
+
+/// Top the treasury up to its rent-exempt minimum, returning that minimum
+///
+/// Payouts can then never take the treasury below it without failing the
+/// solvency check.
+pub(crate) fn fund_treasury_rent<'info>(
+    treasury: &SystemAccount<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<u64> {
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+    let shortfall = rent_exempt_minimum.saturating_sub(treasury.lamports());
+
+    if shortfall > 0 {
+        let cpi_context = CpiContext::new(
+            system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: payer.to_account_info(),
+                to: treasury.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, shortfall)?;
+    }
+
+    Ok(rent_exempt_minimum)
+}
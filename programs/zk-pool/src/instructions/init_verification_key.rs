@@ -0,0 +1,46 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(circuit: u8)]
+pub struct InitVerificationKey<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ZkPoolError::Unauthorized
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = VerificationKeyAccount::space_for(
+            VerificationKeyAccount::expected_public_inputs(circuit)? as u32
+        ),
+        seeds = [VK_SEED, &[circuit]],
+        bump
+    )]
+    pub vk_account: Account<'info, VerificationKeyAccount>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the (empty) VK account for a circuit added after initialize
+pub fn init_verification_key(ctx: Context<InitVerificationKey>, circuit: u8) -> Result<()> {
+    let vk_account = &mut ctx.accounts.vk_account;
+    vk_account.version = ACCOUNT_VERSION;
+    vk_account.circuit = circuit;
+    vk_account.n_public = VerificationKeyAccount::expected_public_inputs(circuit)? as u32;
+    vk_account.vk_data = Vec::new();
+    vk_account.vk_hash = [0u8; 32];
+    vk_account.bump = ctx.bumps.vk_account;
+
+    Ok(())
+}
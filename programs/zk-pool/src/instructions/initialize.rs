@@ -1,6 +1,7 @@
 use crate::constants::*;
 use crate::errors::ZkPoolError;
 use crate::events::Initialized;
+use crate::instructions::init_pool_state::fund_treasury_rent;
 use crate::state::*;
 use anchor_lang::prelude::*;
 
@@ -25,6 +26,15 @@ pub struct Initialize<'info> {
     )]
     pub roots: Account<'info, RootsAccount>,
 
+    #[account(
+        init,
+        payer = admin,
+        space = CommitmentQueue::LEN,
+        seeds = [COMMITMENT_QUEUE_SEED, &0u32.to_le_bytes()],
+        bump
+    )]
+    pub queue: Account<'info, CommitmentQueue>,
+
     #[account(
         init,
         payer = admin,
@@ -61,6 +71,23 @@ pub struct Initialize<'info> {
     )]
     pub vk_unshield: Account<'info, VerificationKeyAccount>,
 
+    #[account(
+        init,
+        payer = admin,
+        space = PoolState::LEN,
+        seeds = [POOL_STATE_SEED],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    /// Treasury PDA, funded up to its rent-exempt minimum
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
     #[account(mut)]
     pub admin: Signer<'info>,
 
@@ -84,30 +111,67 @@ pub fn initialize(
     );
 
     let config = &mut ctx.accounts.config;
+    config.version = ACCOUNT_VERSION;
     config.admin = ctx.accounts.admin.key();
     config.merkle_depth = merkle_depth;
     config.root_window = root_window;
     config.abi_hash = abi_hash;
     config.vk_hashes = VkHashes::default();
-    config.paused = false; // Start unpaused
+    config.paused_mask = 0; // Start unpaused
+    config.paused_at = 0;
+    config.max_pause_duration = DEFAULT_MAX_PAUSE_DURATION;
     config.bump = ctx.bumps.config;
+    config.max_root_age_slots = 0;
+    config.root_attestation = false;
+    config.root_signer = Pubkey::default();
+    config.active_tree = 0;
+    config.batch_append_vk_hash = [0u8; 32];
+    config.disclosure_vk_hash = [0u8; 32];
+    config.require_credential = false;
+    config.credential_issuer = Pubkey::default();
+    config.max_shield_amount = 0;
+    config.max_unshield_amount = 0;
+    config.tvl_cap = 0;
+    config.tvl = 0;
+    config.withdrawal_delay_threshold = 0;
+    config.withdrawal_delay_slots = 0;
+    config.guardian = Pubkey::default();
+    config.top_up_recipients = false;
+    config.shield_fee_bps = 0;
+    config.unshield_fee_bps = 0;
+    config.denomination = 0;
 
     // Initialize roots account
     let roots = &mut ctx.accounts.roots;
+    roots.version = ACCOUNT_VERSION;
     roots.capacity = root_window;
-    roots.roots = vec![[0u8; 32]; root_window as usize];
+    roots.roots = vec![RootEntry::default(); root_window as usize];
+    roots.next_seq = 0;
     roots.cursor = 0;
     roots.size = 0;
     roots.bump = ctx.bumps.roots;
+    roots.tree_index = 0;
+    roots.leaf_count = 0;
+
+    // Initialize commitment queue of tree 0
+    let queue = &mut ctx.accounts.queue;
+    queue.version = ACCOUNT_VERSION;
+    queue.tree_index = 0;
+    queue.current_root = [0u8; 32];
+    queue.next_index = 0;
+    queue.leaves = Vec::new();
+    queue.bump = ctx.bumps.queue;
 
     // Initialize nullifiers account (shard 0)
     let nullifiers = &mut ctx.accounts.nullifiers;
+    nullifiers.version = ACCOUNT_VERSION;
     nullifiers.shard = 0;
     nullifiers.nullifiers = Vec::new();
     nullifiers.bump = ctx.bumps.nullifiers;
 
     // Initialize VK accounts (empty, to be filled by set_verification_key)
     let vk_shield = &mut ctx.accounts.vk_shield;
+    vk_shield.version = ACCOUNT_VERSION;
     vk_shield.circuit = CIRCUIT_SHIELD;
     vk_shield.n_public = SHIELD_PUBLIC_INPUTS as u32;
     vk_shield.vk_data = Vec::new();
@@ -115,6 +179,7 @@ pub fn initialize(
     vk_shield.bump = ctx.bumps.vk_shield;
 
     let vk_transfer = &mut ctx.accounts.vk_transfer;
+    vk_transfer.version = ACCOUNT_VERSION;
     vk_transfer.circuit = CIRCUIT_TRANSFER;
     vk_transfer.n_public = TRANSFER_PUBLIC_INPUTS as u32;
     vk_transfer.vk_data = Vec::new();
@@ -122,12 +187,30 @@ pub fn initialize(
     vk_transfer.bump = ctx.bumps.vk_transfer;
 
     let vk_unshield = &mut ctx.accounts.vk_unshield;
+    vk_unshield.version = ACCOUNT_VERSION;
     vk_unshield.circuit = CIRCUIT_UNSHIELD;
     vk_unshield.n_public = UNSHIELD_PUBLIC_INPUTS as u32;
     vk_unshield.vk_data = Vec::new();
     vk_unshield.vk_hash = [0u8; 32];
     vk_unshield.bump = ctx.bumps.vk_unshield;
 
+    // Start solvency tracking with an empty, rent-exempt treasury
+    fund_treasury_rent(
+        &ctx.accounts.treasury,
+        &ctx.accounts.admin,
+        &ctx.accounts.system_program,
+    )?;
+
+    let pool_state = &mut ctx.accounts.pool_state;
+    pool_state.version = ACCOUNT_VERSION;
+    pool_state.total_deposited = 0;
+    pool_state.total_withdrawn = 0;
+    pool_state.total_fees = 0;
+    pool_state.expected_balance = 0;
+    pool_state.bump = ctx.bumps.pool_state;
+    pool_state.total_protocol_fees = 0;
+    pool_state.reserved = [0u8; POOL_STATE_RESERVED_LEN];
+
     emit!(Initialized {
         admin: config.admin,
         merkle_depth,
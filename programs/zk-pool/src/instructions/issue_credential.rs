@@ -0,0 +1,58 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::CredentialIssued;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(holder: Pubkey)]
+pub struct IssueCredential<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        space = Credential::LEN,
+        seeds = [CREDENTIAL_SEED, holder.as_ref()],
+        bump
+    )]
+    pub credential: Account<'info, Credential>,
+
+    #[account(
+        mut,
+        address = config.credential_issuer @ ZkPoolError::Unauthorized
+    )]
+    pub issuer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Issuing again for the same holder renews the expiry
+pub fn issue_credential(
+    ctx: Context<IssueCredential>,
+    holder: Pubkey,
+    expires_at: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(expires_at > now, ZkPoolError::InvalidCredentialExpiry);
+
+    let credential = &mut ctx.accounts.credential;
+    credential.version = ACCOUNT_VERSION;
+    credential.holder = holder;
+    credential.issuer = ctx.accounts.issuer.key();
+    credential.expires_at = expires_at;
+    credential.bump = ctx.bumps.credential;
+
+    emit!(CredentialIssued {
+        holder,
+        issuer: credential.issuer,
+        expires_at,
+        timestamp: now,
+    });
+
+    Ok(())
+}
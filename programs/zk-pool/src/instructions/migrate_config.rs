@@ -0,0 +1,91 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::{AccountMigrated, PoolStateInitialized};
+use crate::instructions::init_pool_state::start_pool_state;
+use crate::migration::{rewrite_account, upgrade_config};
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// CHECK: Legacy layout cannot be deserialized as PoolConfig;
+    /// owner, discriminator and size are validated by the migration helpers
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    /// Solvency accounting, which legacy pools never had
+    #[account(
+        init,
+        payer = payer,
+        space = PoolState::LEN,
+        seeds = [POOL_STATE_SEED],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    /// Treasury PDA (program-owned, holds pooled SOL/tokens)
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    /// Admin recorded in the legacy config
+    pub admin: Signer<'info>,
+
+    /// Pays the rent for the larger config and the new pool state
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let config_info = ctx.accounts.config.to_account_info();
+
+    let mut upgraded = upgrade_config(&config_info.try_borrow_data()?, timestamp)?;
+    require!(
+        upgraded.admin == ctx.accounts.admin.key(),
+        ZkPoolError::Unauthorized
+    );
+
+    // Start solvency tracking and the TVL from the same treasury surplus, so
+    // both counters agree on what the pool holds
+    let expected_balance = start_pool_state(
+        &mut ctx.accounts.pool_state,
+        ctx.bumps.pool_state,
+        &ctx.accounts.treasury,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )?;
+    upgraded.tvl = expected_balance;
+
+    rewrite_account(
+        &config_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &upgraded,
+        PoolConfig::LEN,
+    )?;
+
+    emit!(AccountMigrated {
+        account: config_info.key(),
+        from_version: 0,
+        to_version: ACCOUNT_VERSION,
+        timestamp,
+    });
+
+    emit!(PoolStateInitialized {
+        expected_balance,
+        admin: ctx.accounts.admin.key(),
+        timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::AccountMigrated;
+use crate::migration::{rewrite_account, upgrade_nullifiers};
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(shard: u16)]
+pub struct MigrateNullifiers<'info> {
+    /// Must already be migrated (migrate_config)
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ZkPoolError::Unauthorized
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    /// CHECK: Legacy layout cannot be deserialized as NullifiersAccount;
+    /// owner, discriminator and size are validated by the migration helpers
+    #[account(
+        mut,
+        seeds = [NULLIFIERS_SEED, &shard.to_le_bytes()],
+        bump
+    )]
+    pub nullifiers: UncheckedAccount<'info>,
+
+    pub admin: Signer<'info>,
+
+    /// Pays the rent for the larger account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_nullifiers(ctx: Context<MigrateNullifiers>, _shard: u16) -> Result<()> {
+    let nullifiers_info = ctx.accounts.nullifiers.to_account_info();
+
+    let upgraded = upgrade_nullifiers(&nullifiers_info.try_borrow_data()?)?;
+
+    rewrite_account(
+        &nullifiers_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &upgraded,
+        NullifiersAccount::space_for(NULLIFIER_SHARD_SIZE),
+    )?;
+
+    emit!(AccountMigrated {
+        account: nullifiers_info.key(),
+        from_version: 0,
+        to_version: ACCOUNT_VERSION,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::AccountMigrated;
+use crate::migration::{legacy_queue_start, rewrite_account, upgrade_roots};
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct MigrateRoots<'info> {
+    /// Must already be migrated (migrate_config)
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ZkPoolError::Unauthorized
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    /// CHECK: Legacy layout cannot be deserialized as RootsAccount;
+    /// owner, discriminator and size are validated by the migration helpers
+    #[account(
+        mut,
+        seeds = [ROOTS_SEED],
+        bump
+    )]
+    pub roots: UncheckedAccount<'info>,
+
+    /// Legacy pools had no commitment queue; create the one for tree 0
+    #[account(
+        init,
+        payer = payer,
+        space = CommitmentQueue::LEN,
+        seeds = [COMMITMENT_QUEUE_SEED, &0u32.to_le_bytes()],
+        bump
+    )]
+    pub queue: Account<'info, CommitmentQueue>,
+
+    pub admin: Signer<'info>,
+
+    /// Pays the rent for the larger account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrade the legacy tree 0 and seed its commitment queue
+///
+/// `legacy_root` and `leaf_count` describe the tree as built by the legacy
+/// program (see `legacy_queue_start`), so the first `batch_append` extends it
+/// instead of starting from an empty tree.
+pub fn migrate_roots(
+    ctx: Context<MigrateRoots>,
+    legacy_root: [u8; 32],
+    leaf_count: u64,
+) -> Result<()> {
+    let roots_info = ctx.accounts.roots.to_account_info();
+
+    let clock = Clock::get()?;
+    let mut upgraded = upgrade_roots(
+        &roots_info.try_borrow_data()?,
+        clock.slot,
+        clock.unix_timestamp,
+    )?;
+    let (current_root, next_index) = legacy_queue_start(
+        &upgraded,
+        legacy_root,
+        leaf_count,
+        ctx.accounts.config.merkle_depth,
+    )?;
+    // Shields reserve leaf indices from leaf_count, so it must match the queue
+    upgraded.leaf_count = next_index;
+    let new_len = RootsAccount::space_for(upgraded.capacity);
+
+    rewrite_account(
+        &roots_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &upgraded,
+        new_len,
+    )?;
+
+    let queue = &mut ctx.accounts.queue;
+    queue.version = ACCOUNT_VERSION;
+    queue.tree_index = 0;
+    queue.current_root = current_root;
+    queue.next_index = next_index;
+    queue.leaves = Vec::new();
+    queue.bump = ctx.bumps.queue;
+
+    emit!(AccountMigrated {
+        account: roots_info.key(),
+        from_version: 0,
+        to_version: ACCOUNT_VERSION,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::AccountMigrated;
+use crate::migration::{rewrite_account, upgrade_verification_key};
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(circuit: u8)]
+pub struct MigrateVerificationKey<'info> {
+    /// Must already be migrated (migrate_config)
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ZkPoolError::Unauthorized
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    /// CHECK: Legacy layout cannot be deserialized as VerificationKeyAccount;
+    /// owner, discriminator and size are validated by the migration helpers
+    #[account(
+        mut,
+        seeds = [VK_SEED, &[circuit]],
+        bump
+    )]
+    pub vk_account: UncheckedAccount<'info>,
+
+    pub admin: Signer<'info>,
+
+    /// Pays the rent for the larger account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_verification_key(ctx: Context<MigrateVerificationKey>, circuit: u8) -> Result<()> {
+    let vk_info = ctx.accounts.vk_account.to_account_info();
+
+    let upgraded = upgrade_verification_key(&vk_info.try_borrow_data()?)?;
+    require!(upgraded.circuit == circuit, ZkPoolError::InvalidCircuitType);
+    let new_len = VerificationKeyAccount::space_for(upgraded.n_public);
+
+    rewrite_account(
+        &vk_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &upgraded,
+        new_len,
+    )?;
+
+    emit!(AccountMigrated {
+        account: vk_info.key(),
+        from_version: 0,
+        to_version: ACCOUNT_VERSION,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
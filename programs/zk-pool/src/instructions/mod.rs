@@ -1,15 +1,99 @@
+pub mod add_association_root;
 pub mod add_root;
+pub mod add_signed_root;
+pub mod approve_proposal;
+pub mod attest_root;
+pub mod batch_append;
+pub mod cancel_withdrawal;
+pub mod claim_withdrawal;
+pub mod close_pending_root;
+pub mod create_multisig;
+pub mod create_proposal;
+pub mod execute_proposal;
+pub mod get_pool_stats;
+pub mod init_pool_state;
+pub mod init_verification_key;
 pub mod initialize;
+pub mod issue_credential;
+pub mod migrate_config;
+pub mod migrate_nullifiers;
+pub mod migrate_roots;
+pub mod migrate_verification_key;
+pub mod revoke_credential;
+pub mod revoke_root;
+pub mod rollover_tree;
+pub mod set_association_curator;
+pub mod set_attesters;
+pub mod set_compliance_authority;
+pub mod set_credential_policy;
+pub mod set_denomination;
+pub mod set_limits;
+pub mod set_max_pause_duration;
+pub mod set_max_root_age;
+pub mod set_multisig_signers;
 pub mod set_paused;
+pub mod set_protocol_fees;
+pub mod set_recipient_denied;
+pub mod set_recipient_top_up;
+pub mod set_root_signer;
+pub mod set_root_window;
 pub mod set_verification_key;
+pub mod set_withdrawal_delay;
+pub mod set_withdrawal_rate_limit;
 pub mod submit_shield;
 pub mod submit_transfer;
 pub mod submit_unshield;
+pub mod submit_unshield_and_call;
+pub mod submit_unshield_delayed;
+pub mod verify_disclosure;
+pub mod withdraw_protocol_fees;
 
+pub use add_association_root::*;
 pub use add_root::*;
+pub use add_signed_root::*;
+pub use approve_proposal::*;
+pub use attest_root::*;
+pub use batch_append::*;
+pub use cancel_withdrawal::*;
+pub use claim_withdrawal::*;
+pub use close_pending_root::*;
+pub use create_multisig::*;
+pub use create_proposal::*;
+pub use execute_proposal::*;
+pub use get_pool_stats::*;
+pub use init_pool_state::*;
+pub use init_verification_key::*;
 pub use initialize::*;
+pub use issue_credential::*;
+pub use migrate_config::*;
+pub use migrate_nullifiers::*;
+pub use migrate_roots::*;
+pub use migrate_verification_key::*;
+pub use revoke_credential::*;
+pub use revoke_root::*;
+pub use rollover_tree::*;
+pub use set_association_curator::*;
+pub use set_attesters::*;
+pub use set_compliance_authority::*;
+pub use set_credential_policy::*;
+pub use set_denomination::*;
+pub use set_limits::*;
+pub use set_max_pause_duration::*;
+pub use set_max_root_age::*;
+pub use set_multisig_signers::*;
 pub use set_paused::*;
+pub use set_protocol_fees::*;
+pub use set_recipient_denied::*;
+pub use set_recipient_top_up::*;
+pub use set_root_signer::*;
+pub use set_root_window::*;
 pub use set_verification_key::*;
+pub use set_withdrawal_delay::*;
+pub use set_withdrawal_rate_limit::*;
 pub use submit_shield::*;
 pub use submit_transfer::*;
 pub use submit_unshield::*;
+pub use submit_unshield_and_call::*;
+pub use submit_unshield_delayed::*;
+pub use verify_disclosure::*;
+pub use withdraw_protocol_fees::*;
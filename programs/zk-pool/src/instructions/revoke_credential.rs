@@ -0,0 +1,51 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::CredentialRevoked;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RevokeCredential<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        seeds = [CREDENTIAL_SEED, credential.holder.as_ref()],
+        bump = credential.bump,
+        close = issuer
+    )]
+    pub credential: Account<'info, Credential>,
+
+    /// CHECK: Rent refund destination, must match credential.issuer
+    #[account(
+        mut,
+        address = credential.issuer @ ZkPoolError::Unauthorized
+    )]
+    pub issuer: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+/// The current issuer, the issuer of this credential or the admin may revoke
+pub fn revoke_credential(ctx: Context<RevokeCredential>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let authority = ctx.accounts.authority.key();
+    require!(
+        authority == config.admin
+            || authority == config.credential_issuer
+            || authority == ctx.accounts.credential.issuer,
+        ZkPoolError::Unauthorized
+    );
+
+    emit!(CredentialRevoked {
+        holder: ctx.accounts.credential.holder,
+        authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::RootRevoked;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RevokeRoot<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ZkPoolError::Unauthorized
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        seeds = [ROOTS_SEED, tree_seed(roots.tree_index).as_slice()],
+        bump = roots.bump
+    )]
+    pub roots: Account<'info, RootsAccount>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn revoke_root(ctx: Context<RevokeRoot>, root: [u8; 32]) -> Result<()> {
+    // Entry stays in the ring buffer (keeping sequence numbers intact)
+    // but contains_root rejects it immediately
+    let entry = ctx.accounts.roots.revoke_root(&root)?;
+
+    emit!(RootRevoked {
+        root,
+        seq: entry.seq,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
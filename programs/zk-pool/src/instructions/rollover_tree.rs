@@ -0,0 +1,95 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::TreeRolledOver;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RolloverTree<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    /// Currently active tree
+    #[account(
+        seeds = [ROOTS_SEED, tree_seed(config.active_tree).as_slice()],
+        bump = current_roots.bump
+    )]
+    pub current_roots: Account<'info, RootsAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = RootsAccount::space_for(config.root_window),
+        seeds = [ROOTS_SEED, tree_seed(config.next_tree()?).as_slice()],
+        bump
+    )]
+    pub new_roots: Account<'info, RootsAccount>,
+
+    /// The next tree is never 0, so tree_seed yields its LE index like other queue seeds
+    #[account(
+        init,
+        payer = payer,
+        space = CommitmentQueue::LEN,
+        seeds = [COMMITMENT_QUEUE_SEED, tree_seed(config.next_tree()?).as_slice()],
+        bump
+    )]
+    pub new_queue: Account<'info, CommitmentQueue>,
+
+    /// Anyone once the active tree is full; the admin can roll over early
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn rollover_tree(ctx: Context<RolloverTree>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let current_roots = &ctx.accounts.current_roots;
+    let authority = ctx.accounts.authority.key();
+
+    require!(
+        authority == config.admin || current_roots.is_full(config.merkle_depth),
+        ZkPoolError::TreeNotFull
+    );
+
+    let previous_tree = config.active_tree;
+    let tree_index = config.next_tree()?;
+
+    // Old trees keep their roots so existing notes stay spendable
+    let new_roots = &mut ctx.accounts.new_roots;
+    new_roots.version = ACCOUNT_VERSION;
+    new_roots.capacity = config.root_window;
+    new_roots.roots = vec![RootEntry::default(); config.root_window as usize];
+    new_roots.cursor = 0;
+    new_roots.size = 0;
+    new_roots.next_seq = 0;
+    new_roots.bump = ctx.bumps.new_roots;
+    new_roots.tree_index = tree_index;
+    new_roots.leaf_count = 0;
+
+    let new_queue = &mut ctx.accounts.new_queue;
+    new_queue.version = ACCOUNT_VERSION;
+    new_queue.tree_index = tree_index;
+    new_queue.current_root = [0u8; 32];
+    new_queue.next_index = 0;
+    new_queue.leaves = Vec::new();
+    new_queue.bump = ctx.bumps.new_queue;
+
+    config.active_tree = tree_index;
+
+    emit!(TreeRolledOver {
+        previous_tree,
+        tree_index,
+        previous_leaf_count: current_roots.leaf_count,
+        authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
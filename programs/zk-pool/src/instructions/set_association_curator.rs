@@ -0,0 +1,47 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::AssociationCuratorSet;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetAssociationCurator<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ZkPoolError::Unauthorized
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = AssociationRoots::LEN,
+        seeds = [ASSOCIATION_ROOTS_SEED],
+        bump
+    )]
+    pub association_roots: Account<'info, AssociationRoots>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Also creates the association set on first use; Pubkey::default() leaves it admin-managed
+pub fn set_association_curator(ctx: Context<SetAssociationCurator>, curator: Pubkey) -> Result<()> {
+    let association_roots = &mut ctx.accounts.association_roots;
+    association_roots.version = ACCOUNT_VERSION;
+    association_roots.curator = curator;
+    association_roots.bump = ctx.bumps.association_roots;
+
+    emit!(AssociationCuratorSet {
+        curator,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::AttestersUpdated;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetAttesters<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ZkPoolError::Unauthorized
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = AttesterSet::LEN,
+        seeds = [ATTESTERS_SEED],
+        bump
+    )]
+    pub attester_set: Account<'info, AttesterSet>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Passing an empty attester list with threshold 0 disables attestation mode
+pub fn set_attesters(
+    ctx: Context<SetAttesters>,
+    attesters: Vec<Pubkey>,
+    threshold: u8,
+    expiry_slots: u64,
+) -> Result<()> {
+    let enabled = !(attesters.is_empty() && threshold == 0);
+    if enabled {
+        AttesterSet::validate(&attesters, threshold, expiry_slots)?;
+    }
+
+    let attester_set = &mut ctx.accounts.attester_set;
+    attester_set.version = ACCOUNT_VERSION;
+    attester_set.attesters = attesters;
+    attester_set.threshold = threshold;
+    attester_set.expiry_slots = expiry_slots;
+    attester_set.bump = ctx.bumps.attester_set;
+
+    let config = &mut ctx.accounts.config;
+    config.root_attestation = enabled;
+
+    emit!(AttestersUpdated {
+        attesters: attester_set.attesters.clone(),
+        threshold,
+        expiry_slots,
+        enabled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::ComplianceAuthoritySet;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetComplianceAuthority<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ZkPoolError::Unauthorized
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = DenyList::LEN,
+        seeds = [DENY_LIST_SEED],
+        bump
+    )]
+    pub deny_list: Account<'info, DenyList>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Also creates the deny-list on first use; Pubkey::default() leaves it admin-managed
+pub fn set_compliance_authority(
+    ctx: Context<SetComplianceAuthority>,
+    compliance: Pubkey,
+) -> Result<()> {
+    let deny_list = &mut ctx.accounts.deny_list;
+    deny_list.version = ACCOUNT_VERSION;
+    deny_list.compliance = compliance;
+    deny_list.bump = ctx.bumps.deny_list;
+
+    emit!(ComplianceAuthoritySet {
+        compliance,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::CredentialPolicySet;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetCredentialPolicy<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ZkPoolError::Unauthorized
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Changing the issuer invalidates credentials issued by the previous one
+pub fn set_credential_policy(
+    ctx: Context<SetCredentialPolicy>,
+    issuer: Pubkey,
+    required: bool,
+) -> Result<()> {
+    require!(
+        !required || issuer != Pubkey::default(),
+        ZkPoolError::CredentialIssuerNotSet
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.require_credential = required;
+    config.credential_issuer = issuer;
+
+    emit!(CredentialPolicySet {
+        required,
+        issuer,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::DenominationSet;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetDenomination<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ZkPoolError::Unauthorized
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Notes of any other amount cannot be unshielded while a denomination is set,
+/// so one can only be chosen while the pool is empty; 0 turns the mode off anytime
+pub fn set_denomination(ctx: Context<SetDenomination>, denomination: u64) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(
+        denomination == 0 || config.tvl == 0,
+        ZkPoolError::PoolNotEmpty
+    );
+    config.denomination = denomination;
+
+    emit!(DenominationSet {
+        denomination,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
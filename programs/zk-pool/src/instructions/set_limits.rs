@@ -0,0 +1,41 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::LimitsSet;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetLimits<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ZkPoolError::Unauthorized
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// A cap below the current TVL only blocks new deposits, withdrawals still work
+pub fn set_limits(
+    ctx: Context<SetLimits>,
+    max_shield_amount: u64,
+    max_unshield_amount: u64,
+    tvl_cap: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.max_shield_amount = max_shield_amount;
+    config.max_unshield_amount = max_unshield_amount;
+    config.tvl_cap = tvl_cap;
+
+    emit!(LimitsSet {
+        max_shield_amount,
+        max_unshield_amount,
+        tvl_cap,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
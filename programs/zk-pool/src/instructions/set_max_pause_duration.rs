@@ -0,0 +1,36 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::MaxPauseDurationSet;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetMaxPauseDuration<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ZkPoolError::Unauthorized
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn set_max_pause_duration(
+    ctx: Context<SetMaxPauseDuration>,
+    max_pause_duration: i64,
+) -> Result<()> {
+    require!(max_pause_duration >= 0, ZkPoolError::InvalidPauseDuration);
+
+    let config = &mut ctx.accounts.config;
+    config.max_pause_duration = max_pause_duration;
+
+    emit!(MaxPauseDurationSet {
+        max_pause_duration,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::MaxRootAgeSet;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetMaxRootAge<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ZkPoolError::Unauthorized
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn set_max_root_age(ctx: Context<SetMaxRootAge>, max_root_age_slots: u64) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.max_root_age_slots = max_root_age_slots;
+
+    emit!(MaxRootAgeSet {
+        max_root_age_slots,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
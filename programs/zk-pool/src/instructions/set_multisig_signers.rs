@@ -0,0 +1,37 @@
+use crate::constants::*;
+use crate::events::MultisigSignersChanged;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetMultisigSigners<'info> {
+    /// Must sign via execute_proposal (PDA signature)
+    #[account(
+        mut,
+        signer,
+        seeds = [MULTISIG_SEED],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+}
+
+pub fn set_multisig_signers(
+    ctx: Context<SetMultisigSigners>,
+    signers: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    Multisig::validate_signers(&signers, threshold)?;
+
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.signers = signers;
+    multisig.threshold = threshold;
+
+    emit!(MultisigSignersChanged {
+        multisig: multisig.key(),
+        signers: multisig.signers.clone(),
+        threshold,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
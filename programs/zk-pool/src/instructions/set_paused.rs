@@ -17,14 +17,17 @@ pub struct SetPaused<'info> {
     pub admin: Signer<'info>,
 }
 
-pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+pub fn set_paused(ctx: Context<SetPaused>, circuit_mask: u8, paused: bool) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
     let config = &mut ctx.accounts.config;
-    config.paused = paused;
+    let changed = config.set_paused(circuit_mask, paused, timestamp)?;
 
     emit!(PoolPausedChanged {
         paused,
+        circuit_mask: changed,
+        paused_mask: config.paused_mask,
         admin: ctx.accounts.admin.key(),
-        timestamp: Clock::get()?.unix_timestamp,
+        timestamp,
     });
 
     Ok(())
@@ -0,0 +1,64 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::ProtocolFeesSet;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetProtocolFees<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ZkPoolError::Unauthorized
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = FeeVault::LEN,
+        seeds = [FEE_VAULT_SEED],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Also creates the fee vault on first use
+pub fn set_protocol_fees(
+    ctx: Context<SetProtocolFees>,
+    shield_fee_bps: u16,
+    unshield_fee_bps: u16,
+    fee_manager: Pubkey,
+) -> Result<()> {
+    require!(
+        shield_fee_bps <= MAX_PROTOCOL_FEE_BPS && unshield_fee_bps <= MAX_PROTOCOL_FEE_BPS,
+        ZkPoolError::InvalidProtocolFee
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.shield_fee_bps = shield_fee_bps;
+    config.unshield_fee_bps = unshield_fee_bps;
+
+    let fee_vault = &mut ctx.accounts.fee_vault;
+    fee_vault.version = ACCOUNT_VERSION;
+    fee_vault.fee_manager = fee_manager;
+    fee_vault.bump = ctx.bumps.fee_vault;
+
+    emit!(ProtocolFeesSet {
+        shield_fee_bps,
+        unshield_fee_bps,
+        fee_manager,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::DenyListUpdated;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetRecipientDenied<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        seeds = [DENY_LIST_SEED],
+        bump = deny_list.bump
+    )]
+    pub deny_list: Account<'info, DenyList>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_recipient_denied(
+    ctx: Context<SetRecipientDenied>,
+    recipient: Pubkey,
+    denied: bool,
+) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let deny_list = &mut ctx.accounts.deny_list;
+
+    require!(
+        deny_list.is_manager(&authority, &ctx.accounts.config.admin),
+        ZkPoolError::Unauthorized
+    );
+
+    // Only actual changes are emitted
+    if deny_list.set_denied(recipient, denied)? {
+        emit!(DenyListUpdated {
+            recipient,
+            denied,
+            authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::RecipientTopUpSet;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetRecipientTopUp<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ZkPoolError::Unauthorized
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn set_recipient_top_up(ctx: Context<SetRecipientTopUp>, enabled: bool) -> Result<()> {
+    ctx.accounts.config.top_up_recipients = enabled;
+
+    emit!(RecipientTopUpSet {
+        enabled,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
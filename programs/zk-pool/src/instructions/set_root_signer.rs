@@ -0,0 +1,32 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::RootSignerSet;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetRootSigner<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ZkPoolError::Unauthorized
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Pubkey::default() disables add_signed_root and re-enables add_root
+pub fn set_root_signer(ctx: Context<SetRootSigner>, root_signer: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.root_signer = root_signer;
+
+    emit!(RootSignerSet {
+        root_signer,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::RootWindowChanged;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(root_window: u16)]
+pub struct SetRootWindow<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ZkPoolError::Unauthorized
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        seeds = [ROOTS_SEED, tree_seed(roots.tree_index).as_slice()],
+        bump = roots.bump,
+        realloc = RootsAccount::space_for(
+            RootsAccount::checked_window(roots.capacity, root_window)?
+        ),
+        realloc::payer = payer,
+        realloc::zero = false
+    )]
+    pub roots: Account<'info, RootsAccount>,
+
+    pub admin: Signer<'info>,
+
+    /// Pays for growth and receives the refund when shrinking
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Resize one tree's root ring buffer
+///
+/// The window is per tree: other trees keep their capacity until resized
+/// themselves, and `config.root_window` becomes the window of trees created by
+/// later rollovers. The range and the per-call growth limit are checked by
+/// `checked_window` before the realloc.
+pub fn set_root_window(ctx: Context<SetRootWindow>, root_window: u16) -> Result<()> {
+    let roots = &mut ctx.accounts.roots;
+    require!(roots.capacity > 0, ZkPoolError::RootsNotInitialized);
+
+    let old_window = roots.capacity;
+    roots.resize(root_window);
+
+    let config = &mut ctx.accounts.config;
+    config.root_window = root_window;
+
+    emit!(RootWindowChanged {
+        tree_index: roots.tree_index,
+        old_window,
+        new_window: root_window,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -16,17 +16,27 @@ pub struct SetVerificationKey<'info> {
     )]
     pub config: Account<'info, PoolConfig>,
 
-    /// VK account must be pre-created with proper space via init instruction
+    /// VK account must be pre-created via init instruction; it is resized
+    /// here when a circuit's public input count changes
     #[account(
         mut,
         seeds = [VK_SEED, &[circuit]],
-        bump
+        bump,
+        realloc = VerificationKeyAccount::space_for(
+            VerificationKeyAccount::expected_public_inputs(circuit)? as u32
+        ),
+        realloc::payer = payer,
+        realloc::zero = false
     )]
     pub vk_account: Account<'info, VerificationKeyAccount>,
 
-    #[account(mut)]
     pub admin: Signer<'info>,
 
+    /// Pays for growth when the public input count increases; kept apart
+    /// from `admin`, which may be the multisig PDA
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -37,15 +47,13 @@ pub fn set_verification_key(
     vk_hash: [u8; 32],
 ) -> Result<()> {
     // Validate circuit type
-    require!(circuit <= CIRCUIT_UNSHIELD, ZkPoolError::InvalidCircuitType);
+    require!(
+        circuit <= CIRCUIT_DISCLOSURE,
+        ZkPoolError::InvalidCircuitType
+    );
 
     // Determine expected public input count
-    let n_public = match circuit {
-        CIRCUIT_SHIELD => SHIELD_PUBLIC_INPUTS as u32,
-        CIRCUIT_TRANSFER => TRANSFER_PUBLIC_INPUTS as u32,
-        CIRCUIT_UNSHIELD => UNSHIELD_PUBLIC_INPUTS as u32,
-        _ => return Err(ZkPoolError::InvalidCircuitType.into()),
-    };
+    let n_public = VerificationKeyAccount::expected_public_inputs(circuit)? as u32;
 
     // Validate VK data length
     let expected_len = VerificationKeyAccount::vk_data_len(n_public);
@@ -75,6 +83,8 @@ pub fn set_verification_key(
         CIRCUIT_SHIELD => config.vk_hashes.shield = vk_hash,
         CIRCUIT_TRANSFER => config.vk_hashes.transfer = vk_hash,
         CIRCUIT_UNSHIELD => config.vk_hashes.unshield = vk_hash,
+        CIRCUIT_BATCH_APPEND => config.batch_append_vk_hash = vk_hash,
+        CIRCUIT_DISCLOSURE => config.disclosure_vk_hash = vk_hash,
         _ => unreachable!(),
     }
 
@@ -0,0 +1,41 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::WithdrawalDelaySet;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetWithdrawalDelay<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ZkPoolError::Unauthorized
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Changes apply to new unshields; queued withdrawals keep their claimable slot
+pub fn set_withdrawal_delay(
+    ctx: Context<SetWithdrawalDelay>,
+    threshold: u64,
+    delay_slots: u64,
+    guardian: Pubkey,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.withdrawal_delay_threshold = threshold;
+    config.withdrawal_delay_slots = delay_slots;
+    config.guardian = guardian;
+
+    emit!(WithdrawalDelaySet {
+        threshold,
+        delay_slots,
+        guardian,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
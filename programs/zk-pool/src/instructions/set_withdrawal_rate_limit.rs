@@ -0,0 +1,52 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::WithdrawalRateLimitSet;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetWithdrawalRateLimit<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ZkPoolError::Unauthorized
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = TreasuryState::LEN,
+        seeds = [TREASURY_STATE_SEED],
+        bump
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Also creates the treasury state on first use; max_outflow = 0 disables the limit
+pub fn set_withdrawal_rate_limit(
+    ctx: Context<SetWithdrawalRateLimit>,
+    max_outflow: u64,
+    window_slots: u64,
+) -> Result<()> {
+    let treasury_state = &mut ctx.accounts.treasury_state;
+    treasury_state.version = ACCOUNT_VERSION;
+    treasury_state.bump = ctx.bumps.treasury_state;
+    treasury_state.configure(max_outflow, window_slots, Clock::get()?.slot)?;
+
+    emit!(WithdrawalRateLimitSet {
+        max_outflow,
+        window_slots,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,13 +1,15 @@
 use crate::constants::*;
 use crate::errors::ZkPoolError;
-use crate::events::NewCommitment;
+use crate::events::{NewCommitment, Shielded};
+use crate::instructions::submit_unshield::update_pool_state;
 use crate::state::*;
-use crate::verifier::verify_proof;
+use crate::verifier::{ciphertext_hash, field_to_u64, verify_proof};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 pub struct SubmitShield<'info> {
     #[account(
+        mut,
         seeds = [CONFIG_SEED],
         bump = config.bump
     )]
@@ -19,23 +21,102 @@ pub struct SubmitShield<'info> {
     )]
     pub vk_account: Account<'info, VerificationKeyAccount>,
 
+    /// Active tree, receives the new commitment
+    #[account(
+        mut,
+        seeds = [ROOTS_SEED, tree_seed(config.active_tree).as_slice()],
+        bump = roots.bump
+    )]
+    pub roots: Account<'info, RootsAccount>,
+
+    /// Active tree's queue for batch_append
+    #[account(
+        mut,
+        seeds = [COMMITMENT_QUEUE_SEED, &config.active_tree.to_le_bytes()],
+        bump = queue.bump
+    )]
+    pub queue: Account<'info, CommitmentQueue>,
+
+    /// Depositor credential, required when config.require_credential is set
+    #[account(
+        seeds = [CREDENTIAL_SEED, user.key().as_ref()],
+        bump = credential.bump
+    )]
+    pub credential: Option<Account<'info, Credential>>,
+
+    /// Treasury PDA (program-owned, holds pooled SOL/tokens)
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    /// Solvency accounting, checked against the treasury balance once it has been created
+    /// CHECK: Address pinned by seeds, contents read and written in update_pool_state
+    #[account(
+        mut,
+        seeds = [POOL_STATE_SEED],
+        bump
+    )]
+    pub pool_state: UncheckedAccount<'info>,
+
+    /// Protocol fee vault, required while the protocol fee is non-zero
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Option<Account<'info, FeeVault>>,
+
+    /// Depositor, funds the shielded amount
+    #[account(mut)]
     pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
 pub fn submit_shield(
     ctx: Context<SubmitShield>,
     proof: Vec<u8>,
     public_inputs: Vec<[u8; 32]>,
+    encrypted_output: Vec<u8>,
 ) -> Result<()> {
-    // Check pool is not paused
-    require!(!ctx.accounts.config.paused, ZkPoolError::PoolPaused);
+    // Check this flow is not paused
+    require!(
+        !ctx.accounts.config.is_paused(CIRCUIT_SHIELD),
+        ZkPoolError::PoolPaused
+    );
+
+    // Permissioned pools only accept deposits from credentialed wallets
+    let config = &ctx.accounts.config;
+    if config.require_credential {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            ctx.accounts
+                .credential
+                .as_ref()
+                .is_some_and(|c| c.is_valid(&config.credential_issuer, now)),
+            ZkPoolError::CredentialRequired
+        );
+    }
 
-    // Validate public input count (shield expects 1: commitment)
+    // Validate public input count (shield expects 3: commitment, amount, ciphertext_hash)
     require!(
         public_inputs.len() == SHIELD_PUBLIC_INPUTS,
         ZkPoolError::InvalidPublicInputCount
     );
 
+    // Bind the note ciphertext to the proof
+    require!(
+        encrypted_output.len() <= MAX_ENCRYPTED_OUTPUT_LEN,
+        ZkPoolError::EncryptedOutputTooLarge
+    );
+    require!(
+        public_inputs[2] == ciphertext_hash(&encrypted_output),
+        ZkPoolError::CiphertextHashMismatch
+    );
+
     // Verify VK hash matches config
     require!(
         ctx.accounts.vk_account.vk_hash == ctx.accounts.config.vk_hashes.shield,
@@ -56,13 +137,72 @@ pub fn submit_shield(
         &ctx.accounts.config.abi_hash,
     )?;
 
+    // Enforce the denomination, per-shield limit and TVL cap on the proven amount
+    let amount = field_to_u64(&public_inputs[1])?;
+    ctx.accounts.config.record_shield(amount)?;
+
     // Extract commitment (index 0)
     let commitment = public_inputs[0];
 
+    // Append to the active tree (fails once full until rollover_tree)
+    let roots = &mut ctx.accounts.roots;
+    let leaf_index = roots.insert_leaf(ctx.accounts.config.merkle_depth)?;
+    ctx.accounts.queue.push(commitment)?;
+
+    // Fund the note: move the proven amount from the depositor to the treasury
+    if amount > 0 {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+    }
+
+    // The protocol fee is paid on top of the note amount, straight to the vault
+    let protocol_fee = ctx.accounts.config.shield_protocol_fee(amount);
+    if protocol_fee > 0 {
+        let fee_vault = ctx
+            .accounts
+            .fee_vault
+            .as_mut()
+            .ok_or(ZkPoolError::FeeVaultRequired)?;
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: fee_vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, protocol_fee)?;
+        fee_vault.record_collected(protocol_fee)?;
+    }
+
+    update_pool_state(
+        &ctx.accounts.pool_state,
+        ctx.accounts.treasury.lamports(),
+        |state| state.record_deposit(amount),
+    )?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    emit!(Shielded {
+        depositor: ctx.accounts.user.key(),
+        amount,
+        protocol_fee,
+        commitment,
+        timestamp,
+    });
+
     emit!(NewCommitment {
         commitment,
+        tree_index: roots.tree_index,
+        leaf_index,
         circuit: CIRCUIT_SHIELD,
-        timestamp: Clock::get()?.unix_timestamp,
+        encrypted_output,
+        timestamp,
     });
 
     Ok(())
@@ -2,10 +2,11 @@ use crate::constants::*;
 use crate::errors::ZkPoolError;
 use crate::events::{NewCommitment, NullifierSpent};
 use crate::state::*;
-use crate::verifier::verify_proof;
+use crate::verifier::{ciphertext_hash, verify_proof};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
+#[instruction(proof: Vec<u8>, public_inputs: Vec<[u8; 32]>, tree_index: u32)]
 pub struct SubmitTransfer<'info> {
     #[account(
         seeds = [CONFIG_SEED],
@@ -19,12 +20,29 @@ pub struct SubmitTransfer<'info> {
     )]
     pub vk_account: Account<'info, VerificationKeyAccount>,
 
+    /// Tree whose root the proof uses
     #[account(
-        seeds = [ROOTS_SEED],
+        seeds = [ROOTS_SEED, tree_seed(tree_index).as_slice()],
         bump = roots.bump
     )]
     pub roots: Account<'info, RootsAccount>,
 
+    /// Active tree, receives the new commitment (may be the same account as `roots`)
+    #[account(
+        mut,
+        seeds = [ROOTS_SEED, tree_seed(config.active_tree).as_slice()],
+        bump = active_roots.bump
+    )]
+    pub active_roots: Account<'info, RootsAccount>,
+
+    /// Active tree's queue for batch_append
+    #[account(
+        mut,
+        seeds = [COMMITMENT_QUEUE_SEED, &config.active_tree.to_le_bytes()],
+        bump = queue.bump
+    )]
+    pub queue: Account<'info, CommitmentQueue>,
+
     #[account(
         mut,
         seeds = [NULLIFIERS_SEED, &[0u8, 0u8]], // Shard 0 for MVP
@@ -42,16 +60,32 @@ pub fn submit_transfer(
     ctx: Context<SubmitTransfer>,
     proof: Vec<u8>,
     public_inputs: Vec<[u8; 32]>,
+    _tree_index: u32, // checked through the roots PDA seeds
+    encrypted_output: Vec<u8>,
 ) -> Result<()> {
-    // Check pool is not paused
-    require!(!ctx.accounts.config.paused, ZkPoolError::PoolPaused);
+    // Check this flow is not paused
+    require!(
+        !ctx.accounts.config.is_paused(CIRCUIT_TRANSFER),
+        ZkPoolError::PoolPaused
+    );
 
-    // Validate public input count (transfer expects 4: root, nullifier, new_commitment, fee)
+    // Validate public input count
+    // (transfer expects 5: root, nullifier, new_commitment, fee, ciphertext_hash)
     require!(
         public_inputs.len() == TRANSFER_PUBLIC_INPUTS,
         ZkPoolError::InvalidPublicInputCount
     );
 
+    // Bind the note ciphertext to the proof
+    require!(
+        encrypted_output.len() <= MAX_ENCRYPTED_OUTPUT_LEN,
+        ZkPoolError::EncryptedOutputTooLarge
+    );
+    require!(
+        public_inputs[4] == ciphertext_hash(&encrypted_output),
+        ZkPoolError::CiphertextHashMismatch
+    );
+
     // Verify VK hash matches config
     require!(
         ctx.accounts.vk_account.vk_hash == ctx.accounts.config.vk_hashes.transfer,
@@ -70,9 +104,14 @@ pub fn submit_transfer(
     let new_commitment = public_inputs[2];
     let _fee = public_inputs[3];
 
-    // Check root is in recent roots (must exist before proof submission)
+    // Check root is in recent roots (must exist before proof submission
+    // and not older than max_root_age_slots)
     require!(
-        ctx.accounts.roots.contains_root(&root),
+        ctx.accounts.roots.contains_root(
+            &root,
+            Clock::get()?.slot,
+            ctx.accounts.config.max_root_age_slots
+        ),
         ZkPoolError::RootNotFound
     );
 
@@ -99,9 +138,14 @@ pub fn submit_transfer(
         &ctx.accounts.config.abi_hash,
     )?;
 
-    // Mark nullifier as spent
+    // Mark nullifier as spent (nullifiers are global across trees)
     nullifiers.mark_spent(nullifier)?;
 
+    // Append the new note to the active tree
+    let active_roots = &mut ctx.accounts.active_roots;
+    let leaf_index = active_roots.insert_leaf(ctx.accounts.config.merkle_depth)?;
+    ctx.accounts.queue.push(new_commitment)?;
+
     let timestamp = Clock::get()?.unix_timestamp;
 
     emit!(NullifierSpent {
@@ -112,7 +156,10 @@ pub fn submit_transfer(
 
     emit!(NewCommitment {
         commitment: new_commitment,
+        tree_index: active_roots.tree_index,
+        leaf_index,
         circuit: CIRCUIT_TRANSFER,
+        encrypted_output,
         timestamp,
     });
 
@@ -2,12 +2,14 @@ use crate::constants::*;
 use crate::errors::ZkPoolError;
 use crate::events::{NullifierSpent, Unshielded};
 use crate::state::*;
-use crate::verifier::verify_proof;
+use crate::verifier::{field_to_u64, verify_proof};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
+#[instruction(proof: Vec<u8>, public_inputs: Vec<[u8; 32]>, tree_index: u32)]
 pub struct SubmitUnshield<'info> {
     #[account(
+        mut,
         seeds = [CONFIG_SEED],
         bump = config.bump
     )]
@@ -19,8 +21,9 @@ pub struct SubmitUnshield<'info> {
     )]
     pub vk_account: Account<'info, VerificationKeyAccount>,
 
+    /// Tree whose root the proof uses
     #[account(
-        seeds = [ROOTS_SEED],
+        seeds = [ROOTS_SEED, tree_seed(tree_index).as_slice()],
         bump = roots.bump
     )]
     pub roots: Account<'info, RootsAccount>,
@@ -32,6 +35,21 @@ pub struct SubmitUnshield<'info> {
     )]
     pub nullifiers: Account<'info, NullifiersAccount>,
 
+    /// Approved association set roots, required when association_root is non-zero
+    #[account(
+        seeds = [ASSOCIATION_ROOTS_SEED],
+        bump = association_roots.bump
+    )]
+    pub association_roots: Option<Account<'info, AssociationRoots>>,
+
+    /// Recipient deny-list; nothing is denied until it has been created
+    /// CHECK: Address pinned by seeds, contents read in submit_unshield
+    #[account(
+        seeds = [DENY_LIST_SEED],
+        bump
+    )]
+    pub deny_list: UncheckedAccount<'info>,
+
     /// Treasury PDA (program-owned, holds pooled SOL/tokens)
     #[account(
         mut,
@@ -40,6 +58,32 @@ pub struct SubmitUnshield<'info> {
     )]
     pub treasury: SystemAccount<'info>,
 
+    /// Solvency accounting, checked against the treasury balance once it has been created
+    /// CHECK: Address pinned by seeds, contents read and written in update_pool_state
+    #[account(
+        mut,
+        seeds = [POOL_STATE_SEED],
+        bump
+    )]
+    pub pool_state: UncheckedAccount<'info>,
+
+    /// Protocol fee vault, required while the protocol fee is non-zero
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Option<Account<'info, FeeVault>>,
+
+    /// Treasury outflow limiter; outflow is unlimited until it has been created
+    /// CHECK: Address pinned by seeds, contents read and written in record_treasury_outflow
+    #[account(
+        mut,
+        seeds = [TREASURY_STATE_SEED],
+        bump
+    )]
+    pub treasury_state: UncheckedAccount<'info>,
+
     /// Recipient's wallet (decoded from public inputs)
     /// CHECK: Derived from proof public inputs
     #[account(mut)]
@@ -55,11 +99,105 @@ pub fn submit_unshield(
     ctx: Context<SubmitUnshield>,
     proof: Vec<u8>,
     public_inputs: Vec<[u8; 32]>,
+    _tree_index: u32, // checked through the roots PDA seeds
 ) -> Result<()> {
-    // Check pool is not paused
-    require!(!ctx.accounts.config.paused, ZkPoolError::PoolPaused);
+    let timestamp = Clock::get()?.unix_timestamp;
+    let nullifiers_bump = ctx.bumps.nullifiers;
+    let accounts = &mut *ctx.accounts;
+
+    let unshield = verify_unshield(
+        &mut accounts.config,
+        &accounts.vk_account,
+        &accounts.roots,
+        &mut accounts.nullifiers,
+        nullifiers_bump,
+        accounts.association_roots.as_deref(),
+        &accounts.deny_list,
+        accounts.recipient.key(),
+        &proof,
+        &public_inputs,
+        &[0u8; 32], // plain withdrawals carry no call
+        timestamp,
+    )?;
+
+    // Large withdrawals must go through the delayed queue
+    require!(
+        !accounts.config.requires_withdrawal_delay(unshield.amount),
+        ZkPoolError::WithdrawalDelayRequired
+    );
 
-    // Validate public input count (unshield expects 6: root, nullifier, recipient_lo, recipient_hi, amount, fee)
+    let payout = pay_unshield(
+        &accounts.config,
+        &accounts.treasury,
+        ctx.bumps.treasury,
+        &accounts.recipient,
+        &accounts.treasury_state,
+        accounts.fee_vault.as_mut(),
+        &accounts.pool_state,
+        &accounts.system_program,
+        unshield.amount,
+        unshield.fee,
+    )?;
+
+    emit!(NullifierSpent {
+        nullifier: unshield.nullifier,
+        circuit: CIRCUIT_UNSHIELD,
+        timestamp,
+    });
+
+    emit!(Unshielded {
+        recipient: unshield.recipient,
+        amount: unshield.amount,
+        fee: unshield.fee,
+        nullifier: unshield.nullifier,
+        association_root: unshield.association_root,
+        top_up: payout.top_up,
+        protocol_fee: payout.protocol_fee,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// Public inputs of an accepted unshield proof
+pub(crate) struct VerifiedUnshield {
+    pub nullifier: [u8; 32],
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub association_root: [u8; 32],
+}
+
+/// Run every unshield check, verify the proof and spend the nullifier
+///
+/// Shared by `submit_unshield`, `submit_unshield_delayed` and
+/// `submit_unshield_and_call`, which differ in when the recipient is paid and
+/// what happens after. `call_hash` is the value the proof must commit to
+/// (zero when there is no call).
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn verify_unshield(
+    config: &mut PoolConfig,
+    vk_account: &VerificationKeyAccount,
+    roots: &RootsAccount,
+    nullifiers: &mut NullifiersAccount,
+    nullifiers_bump: u8,
+    association_roots: Option<&AssociationRoots>,
+    deny_list: &AccountInfo,
+    recipient: Pubkey,
+    proof: &[u8],
+    public_inputs: &[[u8; 32]],
+    call_hash: &[u8; 32],
+    timestamp: i64,
+) -> Result<VerifiedUnshield> {
+    // Check this flow is not paused (reopens after max_pause_duration)
+    require!(
+        !config.is_unshield_paused(timestamp),
+        ZkPoolError::PoolPaused
+    );
+
+    // Validate public input count
+    // (unshield expects 8: root, nullifier, recipient_lo, recipient_hi, amount, fee,
+    // association_root, call_hash)
     require!(
         public_inputs.len() == UNSHIELD_PUBLIC_INPUTS,
         ZkPoolError::InvalidPublicInputCount
@@ -67,13 +205,13 @@ pub fn submit_unshield(
 
     // Verify VK hash matches config
     require!(
-        ctx.accounts.vk_account.vk_hash == ctx.accounts.config.vk_hashes.unshield,
+        vk_account.vk_hash == config.vk_hashes.unshield,
         ZkPoolError::VkHashMismatch
     );
 
     // Verify VK is set (not zero hash)
     require!(
-        ctx.accounts.config.vk_hashes.unshield != [0u8; 32],
+        config.vk_hashes.unshield != [0u8; 32],
         ZkPoolError::VkNotSet
     );
 
@@ -84,32 +222,43 @@ pub fn submit_unshield(
     let recipient_hi = public_inputs[3];
     let public_amount = public_inputs[4];
     let fee = public_inputs[5];
+    let association_root = public_inputs[6];
+
+    // The proof must commit to exactly the call this instruction will make
+    check_call_hash(&public_inputs[7], call_hash)?;
 
     // Reconstruct recipient address from two-limb encoding (LE within limbs)
     let recipient_pubkey = reconstruct_recipient(recipient_lo, recipient_hi)?;
 
     // Verify recipient matches the provided account
-    require!(
-        recipient_pubkey == ctx.accounts.recipient.key(),
-        ZkPoolError::InvalidRecipient
-    );
+    require!(recipient_pubkey == recipient, ZkPoolError::InvalidRecipient);
 
     // Validate recipient address round-trip (sanity check)
     validate_recipient_roundtrip(&recipient_pubkey, recipient_lo, recipient_hi)?;
 
-    // Check root is in recent roots (must exist before proof submission)
+    check_recipient_allowed(deny_list, &recipient_pubkey)?;
+
+    // Check root is in recent roots (must exist before proof submission
+    // and not older than max_root_age_slots)
     require!(
-        ctx.accounts.roots.contains_root(&root),
+        roots.contains_root(&root, Clock::get()?.slot, config.max_root_age_slots),
         ZkPoolError::RootNotFound
     );
 
+    // A non-zero association root must be approved; zero means the user opted out
+    if association_root != [0u8; 32] {
+        require!(
+            association_roots.is_some_and(|set| set.contains_root(&association_root)),
+            ZkPoolError::AssociationRootNotFound
+        );
+    }
+
     // Initialize nullifiers account if needed
-    let nullifiers = &mut ctx.accounts.nullifiers;
     if nullifiers.nullifiers.is_empty() {
         let shard = get_nullifier_shard(&nullifier);
         nullifiers.shard = shard;
         nullifiers.nullifiers = Vec::new();
-        nullifiers.bump = ctx.bumps.nullifiers;
+        nullifiers.bump = nullifiers_bump;
     }
 
     // Check nullifier not spent
@@ -119,12 +268,7 @@ pub fn submit_unshield(
     );
 
     // Verify proof
-    verify_proof(
-        &ctx.accounts.vk_account,
-        &proof,
-        &public_inputs,
-        &ctx.accounts.config.abi_hash,
-    )?;
+    verify_proof(vk_account, proof, public_inputs, &config.abi_hash)?;
 
     // Mark nullifier as spent
     nullifiers.mark_spent(nullifier)?;
@@ -135,24 +279,104 @@ pub fn submit_unshield(
 
     // Validate amounts
     require!(fee_amount <= amount, ZkPoolError::FeeExceedsAmount);
+    config.record_unshield(amount)?;
 
+    Ok(VerifiedUnshield {
+        nullifier,
+        recipient: recipient_pubkey,
+        amount,
+        fee: fee_amount,
+        association_root,
+    })
+}
+
+/// Require the proof's `call_hash` input to match the call being made
+pub(crate) fn check_call_hash(committed: &[u8; 32], expected: &[u8; 32]) -> Result<()> {
+    require!(committed == expected, ZkPoolError::CallHashMismatch);
+    Ok(())
+}
+
+/// Block payouts to denied recipients (the PDA cannot be swapped, so the
+/// check cannot be skipped once the list exists)
+pub(crate) fn check_recipient_allowed(deny_list: &AccountInfo, recipient: &Pubkey) -> Result<()> {
+    if deny_list.owner == &crate::ID && !deny_list.data_is_empty() {
+        let deny_list = DenyList::try_deserialize(&mut &deny_list.try_borrow_data()?[..])?;
+        require!(
+            !deny_list.is_denied(recipient),
+            ZkPoolError::RecipientDenied
+        );
+    }
+
+    Ok(())
+}
+
+/// Plan the payout of an unshield and pre-check both sides stay rent-exempt
+pub(crate) fn prepare_payout(
+    config: &PoolConfig,
+    treasury: &AccountInfo,
+    recipient: &AccountInfo,
+    amount: u64,
+    fee: u64,
+) -> Result<Payout> {
+    let rent = Rent::get()?;
+    let payout = config.plan_payout(
+        amount,
+        fee,
+        recipient.lamports(),
+        rent.minimum_balance(recipient.data_len()),
+    )?;
+
+    let remaining = treasury
+        .lamports()
+        .checked_sub(payout.paid)
+        .and_then(|rest| rest.checked_sub(payout.protocol_fee))
+        .ok_or(ZkPoolError::TreasuryRentExemption)?;
+    require!(
+        remaining >= rent.minimum_balance(0),
+        ZkPoolError::TreasuryRentExemption
+    );
+
+    Ok(payout)
+}
+
+/// Pay an accepted unshield out of the treasury and update solvency accounting
+///
+/// Shared by every path that releases funds: `submit_unshield`,
+/// `submit_unshield_and_call` and `claim_withdrawal`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn pay_unshield<'info>(
+    config: &PoolConfig,
+    treasury: &SystemAccount<'info>,
+    treasury_bump: u8,
+    recipient: &AccountInfo<'info>,
+    treasury_state: &AccountInfo<'info>,
+    fee_vault: Option<&mut Account<'info, FeeVault>>,
+    pool_state: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+    fee: u64,
+) -> Result<Payout> {
     // Transfer funds to recipient (SOL for MVP)
     // In production, this would handle SPL tokens via treasury ATA
-    let transfer_amount = amount
-        .checked_sub(fee_amount)
-        .ok_or(ZkPoolError::ArithmeticOverflow)?;
+    let payout = prepare_payout(config, treasury, recipient, amount, fee)?;
+    let transfer_amount = payout.paid;
+
+    record_treasury_outflow(
+        treasury_state,
+        transfer_amount.saturating_add(payout.protocol_fee),
+    )?;
 
     if transfer_amount > 0 {
         // Safe CPI transfer using System Program (instead of manual lamport mutation)
         // Treasury is a PDA owned by this program, so we use invoke_signed
-        let treasury_seeds = &[TREASURY_SEED, &[ctx.bumps.treasury]];
+        let treasury_seeds = &[TREASURY_SEED, &[treasury_bump]];
         let signer_seeds = &[&treasury_seeds[..]];
 
         let cpi_context = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
+            system_program.to_account_info(),
             anchor_lang::system_program::Transfer {
-                from: ctx.accounts.treasury.to_account_info(),
-                to: ctx.accounts.recipient.to_account_info(),
+                from: treasury.to_account_info(),
+                to: recipient.clone(),
             },
             signer_seeds,
         );
@@ -160,21 +384,81 @@ pub fn submit_unshield(
         anchor_lang::system_program::transfer(cpi_context, transfer_amount)?;
     }
 
-    let timestamp = Clock::get()?.unix_timestamp;
+    collect_protocol_fee(
+        fee_vault,
+        treasury,
+        treasury_bump,
+        system_program,
+        payout.protocol_fee,
+    )?;
 
-    emit!(NullifierSpent {
-        nullifier,
-        circuit: CIRCUIT_UNSHIELD,
-        timestamp,
-    });
+    update_pool_state(pool_state, treasury.lamports(), |state| {
+        state.record_withdrawal(transfer_amount, payout.retained_fee, payout.protocol_fee)
+    })?;
 
-    emit!(Unshielded {
-        recipient: recipient_pubkey,
-        amount,
-        fee: fee_amount,
-        nullifier,
-        timestamp,
-    });
+    Ok(payout)
+}
+
+/// Move the unshield protocol fee from the treasury to the fee vault
+pub(crate) fn collect_protocol_fee<'info>(
+    fee_vault: Option<&mut Account<'info, FeeVault>>,
+    treasury: &SystemAccount<'info>,
+    treasury_bump: u8,
+    system_program: &Program<'info, System>,
+    protocol_fee: u64,
+) -> Result<()> {
+    if protocol_fee == 0 {
+        return Ok(());
+    }
+
+    let fee_vault = fee_vault.ok_or(ZkPoolError::FeeVaultRequired)?;
+    let treasury_seeds = &[TREASURY_SEED, &[treasury_bump]];
+    let signer_seeds = &[&treasury_seeds[..]];
+
+    let cpi_context = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        anchor_lang::system_program::Transfer {
+            from: treasury.to_account_info(),
+            to: fee_vault.to_account_info(),
+        },
+        signer_seeds,
+    );
+    anchor_lang::system_program::transfer(cpi_context, protocol_fee)?;
+
+    fee_vault.record_collected(protocol_fee)
+}
+
+/// Count a payout against the treasury rate limit, if one has been set up
+///
+/// Like the deny-list, the PDA is pinned by seeds so the limit cannot be
+/// bypassed once the account exists.
+pub(crate) fn record_treasury_outflow(treasury_state: &AccountInfo, amount: u64) -> Result<()> {
+    if treasury_state.owner == &crate::ID && !treasury_state.data_is_empty() {
+        let mut state =
+            TreasuryState::try_deserialize(&mut &treasury_state.try_borrow_data()?[..])?;
+        state.record_outflow(amount, Clock::get()?.slot)?;
+        state.try_serialize(&mut &mut treasury_state.try_borrow_mut_data()?[..])?;
+    }
+
+    Ok(())
+}
+
+/// Apply `update` to the solvency accounting, if it has been set up, and check
+/// the treasury still covers the expected balance
+///
+/// Pinned by seeds like the treasury limiter, so tracking cannot be skipped
+/// once the account exists.
+pub(crate) fn update_pool_state(
+    pool_state: &AccountInfo,
+    treasury_balance: u64,
+    update: impl FnOnce(&mut PoolState) -> Result<()>,
+) -> Result<()> {
+    if pool_state.owner == &crate::ID && !pool_state.data_is_empty() {
+        let mut state = PoolState::try_deserialize(&mut &pool_state.try_borrow_data()?[..])?;
+        update(&mut state)?;
+        state.check_solvency(treasury_balance, Rent::get()?.minimum_balance(0))?;
+        state.try_serialize(&mut &mut pool_state.try_borrow_mut_data()?[..])?;
+    }
 
     Ok(())
 }
@@ -207,18 +491,3 @@ fn validate_recipient_roundtrip(pubkey: &Pubkey, lo: [u8; 32], hi: [u8; 32]) ->
 
     Ok(())
 }
-
-/// Convert field element bytes to u64 (assuming little-endian encoding)
-fn field_to_u64(field: &[u8; 32]) -> Result<u64> {
-    // Take first 8 bytes as little-endian u64
-    let mut bytes = [0u8; 8];
-    bytes.copy_from_slice(&field[..8]);
-    let value = u64::from_le_bytes(bytes);
-
-    // Verify remaining bytes are zero (amount must fit in u64)
-    for &b in &field[8..] {
-        require!(b == 0, ZkPoolError::AmountTooLarge);
-    }
-
-    Ok(value)
-}
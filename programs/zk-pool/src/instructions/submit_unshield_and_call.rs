@@ -0,0 +1,337 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::{NullifierSpent, UnshieldCallExecuted, Unshielded};
+use crate::instructions::submit_unshield::{pay_unshield, verify_unshield};
+use crate::state::*;
+use crate::verifier::call_hash;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+#[derive(Accounts)]
+#[instruction(proof: Vec<u8>, public_inputs: Vec<[u8; 32]>, tree_index: u32)]
+pub struct SubmitUnshieldAndCall<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        seeds = [VK_SEED, &[CIRCUIT_UNSHIELD]],
+        bump = vk_account.bump
+    )]
+    pub vk_account: Account<'info, VerificationKeyAccount>,
+
+    /// Tree whose root the proof uses
+    #[account(
+        seeds = [ROOTS_SEED, tree_seed(tree_index).as_slice()],
+        bump = roots.bump
+    )]
+    pub roots: Account<'info, RootsAccount>,
+
+    #[account(
+        mut,
+        seeds = [NULLIFIERS_SEED, &[0u8, 0u8]], // Shard 0 for MVP
+        bump
+    )]
+    pub nullifiers: Account<'info, NullifiersAccount>,
+
+    /// Approved association set roots, required when association_root is non-zero
+    #[account(
+        seeds = [ASSOCIATION_ROOTS_SEED],
+        bump = association_roots.bump
+    )]
+    pub association_roots: Option<Account<'info, AssociationRoots>>,
+
+    /// Recipient deny-list; nothing is denied until it has been created
+    /// CHECK: Address pinned by seeds, contents read in verify_unshield
+    #[account(
+        seeds = [DENY_LIST_SEED],
+        bump
+    )]
+    pub deny_list: UncheckedAccount<'info>,
+
+    /// Treasury PDA (program-owned, holds pooled SOL/tokens)
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    /// Solvency accounting, checked against the treasury balance once it has been created
+    /// CHECK: Address pinned by seeds, contents read and written in update_pool_state
+    #[account(
+        mut,
+        seeds = [POOL_STATE_SEED],
+        bump
+    )]
+    pub pool_state: UncheckedAccount<'info>,
+
+    /// Protocol fee vault, required while the protocol fee is non-zero
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Option<Account<'info, FeeVault>>,
+
+    /// Treasury outflow limiter; outflow is unlimited until it has been created
+    /// CHECK: Address pinned by seeds, contents read and written in record_treasury_outflow
+    #[account(
+        mut,
+        seeds = [TREASURY_STATE_SEED],
+        bump
+    )]
+    pub treasury_state: UncheckedAccount<'info>,
+
+    /// Pool PDA that receives the payout and signs the call; the proof's
+    /// recipient must be this address
+    #[account(
+        mut,
+        seeds = [CALL_AUTHORITY_SEED],
+        bump
+    )]
+    pub call_authority: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Program invoked after the payout, with the remaining accounts
+    /// CHECK: Bound to the proof through call_hash, checked to be executable
+    pub target_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Unshield, then invoke `target_program` with `data` in the same transaction
+///
+/// The payout goes to the `call_authority` PDA, which signs the call so the
+/// target can pull the funds; no wallet has to sign or receive them. The
+/// remaining accounts are passed through to the target in order with their
+/// writable flags, and only `call_authority` is marked as a signer. The proof
+/// commits to `call_hash(target_program, accounts, data)`, so a relayer can
+/// change neither the program, the data nor any account of the call.
+pub fn submit_unshield_and_call<'info>(
+    ctx: Context<'_, '_, '_, 'info, SubmitUnshieldAndCall<'info>>,
+    proof: Vec<u8>,
+    public_inputs: Vec<[u8; 32]>,
+    _tree_index: u32, // checked through the roots PDA seeds
+    data: Vec<u8>,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let nullifiers_bump = ctx.bumps.nullifiers;
+    let accounts = &mut *ctx.accounts;
+
+    // Calling back into the pool could act on state this instruction has
+    // not written back yet
+    let target = &accounts.target_program;
+    require!(
+        target.executable && target.key() != crate::ID,
+        ZkPoolError::InvalidCallTarget
+    );
+
+    let call_authority = accounts.call_authority.key();
+    let metas = call_metas(ctx.remaining_accounts, &call_authority);
+    let expected_call_hash = call_hash(&target.key(), &metas, &data);
+
+    let unshield = verify_unshield(
+        &mut accounts.config,
+        &accounts.vk_account,
+        &accounts.roots,
+        &mut accounts.nullifiers,
+        nullifiers_bump,
+        accounts.association_roots.as_deref(),
+        &accounts.deny_list,
+        call_authority,
+        &proof,
+        &public_inputs,
+        &expected_call_hash,
+        timestamp,
+    )?;
+
+    // Large withdrawals must go through the delayed queue
+    require!(
+        !accounts.config.requires_withdrawal_delay(unshield.amount),
+        ZkPoolError::WithdrawalDelayRequired
+    );
+
+    let balance_before = accounts.call_authority.lamports();
+    let payout = pay_unshield(
+        &accounts.config,
+        &accounts.treasury,
+        ctx.bumps.treasury,
+        &accounts.call_authority,
+        &accounts.treasury_state,
+        accounts.fee_vault.as_mut(),
+        &accounts.pool_state,
+        &accounts.system_program,
+        unshield.amount,
+        unshield.fee,
+    )?;
+
+    emit!(NullifierSpent {
+        nullifier: unshield.nullifier,
+        circuit: CIRCUIT_UNSHIELD,
+        timestamp,
+    });
+
+    emit!(Unshielded {
+        recipient: unshield.recipient,
+        amount: unshield.amount,
+        fee: unshield.fee,
+        nullifier: unshield.nullifier,
+        association_root: unshield.association_root,
+        top_up: payout.top_up,
+        protocol_fee: payout.protocol_fee,
+        timestamp,
+    });
+
+    // Every check and payout is done before handing control to the target
+    let remaining = ctx.remaining_accounts;
+    let ix = Instruction {
+        program_id: target.key(),
+        accounts: metas,
+        data,
+    };
+
+    let mut infos = remaining.to_vec();
+    infos.push(target.to_account_info());
+    infos.push(accounts.call_authority.to_account_info());
+    let call_authority_seeds = &[CALL_AUTHORITY_SEED, &[ctx.bumps.call_authority]];
+    invoke_signed(&ix, &infos, &[&call_authority_seeds[..]])?;
+
+    // Anything left behind would be spendable by the next call
+    require!(
+        accounts.call_authority.lamports() <= balance_before,
+        ZkPoolError::CallPayoutNotSpent
+    );
+
+    emit!(UnshieldCallExecuted {
+        nullifier: unshield.nullifier,
+        target_program: target.key(),
+        call_hash: expected_call_hash,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// Account metas of the call, in the order and with the writable flags they are
+/// passed in; `call_authority` is the only signer the target sees
+pub(crate) fn call_metas(accounts: &[AccountInfo], call_authority: &Pubkey) -> Vec<AccountMeta> {
+    accounts
+        .iter()
+        .map(|info| AccountMeta {
+            pubkey: info.key(),
+            is_signer: info.key == call_authority,
+            is_writable: info.is_writable,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::submit_unshield::check_call_hash;
+
+    #[test]
+    fn test_swapped_call_account_is_rejected() {
+        let target = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let obligation = Pubkey::new_unique();
+        let other_obligation = Pubkey::new_unique();
+        let (mut lamports_a, mut lamports_b) = (0u64, 0u64);
+        let (mut data_a, mut data_b) = ([0u8; 0], [0u8; 0]);
+
+        let committed_info = AccountInfo::new(
+            &obligation,
+            false,
+            true,
+            &mut lamports_a,
+            &mut data_a,
+            &owner,
+            false,
+            0,
+        );
+        let committed = call_hash(
+            &target,
+            &call_metas(&[committed_info], &Pubkey::new_unique()),
+            b"deposit",
+        );
+
+        // The same call with the proven account passes
+        let metas = [AccountMeta::new(obligation, false)];
+        check_call_hash(&committed, &call_hash(&target, &metas, b"deposit")).unwrap();
+
+        // A relayer swapping in another destination account is rejected
+        let swapped_info = AccountInfo::new(
+            &other_obligation,
+            false,
+            true,
+            &mut lamports_b,
+            &mut data_b,
+            &owner,
+            false,
+            0,
+        );
+        let swapped = call_hash(
+            &target,
+            &call_metas(&[swapped_info], &Pubkey::new_unique()),
+            b"deposit",
+        );
+        assert_eq!(
+            check_call_hash(&committed, &swapped).unwrap_err(),
+            ZkPoolError::CallHashMismatch.into()
+        );
+
+        // So is passing the proven account with different flags
+        let readonly = [AccountMeta::new_readonly(obligation, false)];
+        assert_eq!(
+            check_call_hash(&committed, &call_hash(&target, &readonly, b"deposit")).unwrap_err(),
+            ZkPoolError::CallHashMismatch.into()
+        );
+    }
+
+    #[test]
+    fn test_only_call_authority_signs() {
+        let owner = Pubkey::new_unique();
+        let (call_authority, _) = Pubkey::find_program_address(&[CALL_AUTHORITY_SEED], &crate::ID);
+        let relayer = Pubkey::new_unique();
+        let (mut lamports_a, mut lamports_b) = (0u64, 0u64);
+        let (mut data_a, mut data_b) = ([0u8; 0], [0u8; 0]);
+
+        // A signer of the outer transaction is forwarded without its signature
+        let relayer_info = AccountInfo::new(
+            &relayer,
+            true,
+            true,
+            &mut lamports_a,
+            &mut data_a,
+            &owner,
+            false,
+            0,
+        );
+        let authority_info = AccountInfo::new(
+            &call_authority,
+            false,
+            true,
+            &mut lamports_b,
+            &mut data_b,
+            &owner,
+            false,
+            0,
+        );
+
+        let metas = call_metas(&[relayer_info, authority_info], &call_authority);
+        assert_eq!(
+            metas,
+            vec![
+                AccountMeta::new(relayer, false),
+                AccountMeta::new(call_authority, true),
+            ]
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use crate::constants::*;
+use crate::events::{NullifierSpent, WithdrawalQueued};
+use crate::instructions::submit_unshield::verify_unshield;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(proof: Vec<u8>, public_inputs: Vec<[u8; 32]>, tree_index: u32)]
+pub struct SubmitUnshieldDelayed<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        seeds = [VK_SEED, &[CIRCUIT_UNSHIELD]],
+        bump = vk_account.bump
+    )]
+    pub vk_account: Account<'info, VerificationKeyAccount>,
+
+    /// Tree whose root the proof uses
+    #[account(
+        seeds = [ROOTS_SEED, tree_seed(tree_index).as_slice()],
+        bump = roots.bump
+    )]
+    pub roots: Account<'info, RootsAccount>,
+
+    #[account(
+        mut,
+        seeds = [NULLIFIERS_SEED, &[0u8, 0u8]], // Shard 0 for MVP
+        bump
+    )]
+    pub nullifiers: Account<'info, NullifiersAccount>,
+
+    /// Approved association set roots, required when association_root is non-zero
+    #[account(
+        seeds = [ASSOCIATION_ROOTS_SEED],
+        bump = association_roots.bump
+    )]
+    pub association_roots: Option<Account<'info, AssociationRoots>>,
+
+    /// Recipient deny-list; nothing is denied until it has been created
+    /// CHECK: Address pinned by seeds, contents read in verify_unshield
+    #[account(
+        seeds = [DENY_LIST_SEED],
+        bump
+    )]
+    pub deny_list: UncheckedAccount<'info>,
+
+    /// Queued withdrawal, keyed by the spent nullifier
+    #[account(
+        init,
+        payer = user,
+        space = PendingWithdrawal::LEN,
+        seeds = [
+            PENDING_WITHDRAWAL_SEED,
+            PendingWithdrawal::nullifier_seed(&public_inputs).as_ref()
+        ],
+        bump
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+
+    /// Recipient's wallet (decoded from public inputs), paid on claim
+    /// CHECK: Derived from proof public inputs
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Verify an unshield now and hold the payout for `withdrawal_delay_slots`
+///
+/// The nullifier is spent immediately; the funds stay in the treasury until
+/// `claim_withdrawal`, so a guardian can still stop a bad proof.
+pub fn submit_unshield_delayed(
+    ctx: Context<SubmitUnshieldDelayed>,
+    proof: Vec<u8>,
+    public_inputs: Vec<[u8; 32]>,
+    _tree_index: u32, // checked through the roots PDA seeds
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let timestamp = clock.unix_timestamp;
+    let nullifiers_bump = ctx.bumps.nullifiers;
+    let accounts = &mut *ctx.accounts;
+
+    let unshield = verify_unshield(
+        &mut accounts.config,
+        &accounts.vk_account,
+        &accounts.roots,
+        &mut accounts.nullifiers,
+        nullifiers_bump,
+        accounts.association_roots.as_deref(),
+        &accounts.deny_list,
+        accounts.recipient.key(),
+        &proof,
+        &public_inputs,
+        &[0u8; 32], // queued withdrawals carry no call
+        timestamp,
+    )?;
+
+    let claimable_slot = clock
+        .slot
+        .saturating_add(accounts.config.withdrawal_delay_slots);
+
+    let pending = &mut accounts.pending_withdrawal;
+    pending.version = ACCOUNT_VERSION;
+    pending.nullifier = unshield.nullifier;
+    pending.recipient = unshield.recipient;
+    pending.amount = unshield.amount;
+    pending.fee = unshield.fee;
+    pending.association_root = unshield.association_root;
+    pending.claimable_slot = claimable_slot;
+    pending.payer = accounts.user.key();
+    pending.bump = ctx.bumps.pending_withdrawal;
+    pending.reserved = [0u8; PENDING_WITHDRAWAL_RESERVED_LEN];
+
+    emit!(NullifierSpent {
+        nullifier: unshield.nullifier,
+        circuit: CIRCUIT_UNSHIELD,
+        timestamp,
+    });
+
+    emit!(WithdrawalQueued {
+        nullifier: unshield.nullifier,
+        recipient: unshield.recipient,
+        amount: unshield.amount,
+        fee: unshield.fee,
+        claimable_slot,
+        timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,197 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::DisclosureVerified;
+use crate::state::*;
+use crate::verifier::{field_to_u64, verify_proof};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(proof: Vec<u8>, public_inputs: Vec<[u8; 32]>, tree_index: u32)]
+pub struct VerifyDisclosure<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PoolConfig>,
+
+    #[account(
+        seeds = [VK_SEED, &[CIRCUIT_DISCLOSURE]],
+        bump = vk_account.bump
+    )]
+    pub vk_account: Account<'info, VerificationKeyAccount>,
+
+    /// Tree whose root the proof uses
+    #[account(
+        seeds = [ROOTS_SEED, tree_seed(tree_index).as_slice()],
+        bump = roots.bump
+    )]
+    pub roots: Account<'info, RootsAccount>,
+
+    pub requester: Signer<'info>,
+}
+
+/// Read-only: verifies the statement and emits it, pool state is untouched
+pub fn verify_disclosure(
+    ctx: Context<VerifyDisclosure>,
+    proof: Vec<u8>,
+    public_inputs: Vec<[u8; 32]>,
+    tree_index: u32,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+
+    // Verify VK is set (not zero hash) and matches config
+    require!(
+        config.disclosure_vk_hash != [0u8; 32],
+        ZkPoolError::VkNotSet
+    );
+    require!(
+        ctx.accounts.vk_account.vk_hash == config.disclosure_vk_hash,
+        ZkPoolError::VkHashMismatch
+    );
+
+    let (total, commitments) =
+        disclosure_statement(&public_inputs, &ctx.accounts.roots, Clock::get()?.slot)?;
+    let root = public_inputs[0];
+    let viewing_key = public_inputs[1];
+
+    verify_proof(
+        &ctx.accounts.vk_account,
+        &proof,
+        &public_inputs,
+        &config.abi_hash,
+    )?;
+
+    emit!(DisclosureVerified {
+        tree_index,
+        root,
+        viewing_key,
+        total,
+        commitments,
+        requester: ctx.accounts.requester.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Check the public inputs against the tree, returning the total and the
+/// non-padding commitments
+pub(crate) fn disclosure_statement(
+    public_inputs: &[[u8; 32]],
+    roots: &RootsAccount,
+    current_slot: u64,
+) -> Result<(u64, Vec<[u8; 32]>)> {
+    // Validate public input count (root, viewing_key, total, commitments)
+    require!(
+        public_inputs.len() == DISCLOSURE_PUBLIC_INPUTS,
+        ZkPoolError::InvalidPublicInputCount
+    );
+
+    // Extract public inputs per ABI.md ordering
+    let root = public_inputs[0];
+    let total = field_to_u64(&public_inputs[2])?;
+
+    // Disclosures cover history, so any root still in the window is accepted
+    // regardless of max_root_age_slots
+    require!(
+        roots.contains_root(&root, current_slot, 0),
+        ZkPoolError::RootNotFound
+    );
+
+    // Zero entries are padding; the circuit cannot stop a note being listed
+    // twice, so duplicates are rejected here to keep the total honest
+    let commitments: Vec<[u8; 32]> = public_inputs[3..]
+        .iter()
+        .filter(|c| **c != [0u8; 32])
+        .copied()
+        .collect();
+    require!(!commitments.is_empty(), ZkPoolError::InvalidDisclosure);
+    for (i, commitment) in commitments.iter().enumerate() {
+        require!(
+            !commitments[..i].contains(commitment),
+            ZkPoolError::InvalidDisclosure
+        );
+    }
+
+    Ok((total, commitments))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roots_at(slot: u64, root: [u8; 32]) -> RootsAccount {
+        let mut roots = RootsAccount {
+            version: ACCOUNT_VERSION,
+            roots: vec![RootEntry::default(); 4],
+            cursor: 0,
+            size: 0,
+            capacity: 4,
+            next_seq: 0,
+            bump: 255,
+            tree_index: 0,
+            leaf_count: 0,
+            reserved: [0u8; ROOTS_RESERVED_LEN],
+        };
+        roots.add_root(root, slot, 0).unwrap();
+        roots
+    }
+
+    fn field(value: u64) -> [u8; 32] {
+        let mut field = [0u8; 32];
+        field[..8].copy_from_slice(&value.to_le_bytes());
+        field
+    }
+
+    /// root, viewing key, total, then the commitments padded with zeros
+    fn inputs(total: u64, commitments: &[[u8; 32]]) -> Vec<[u8; 32]> {
+        let mut inputs = vec![[0xaa; 32], [0xbb; 32], field(total)];
+        inputs.extend_from_slice(commitments);
+        inputs.resize(DISCLOSURE_PUBLIC_INPUTS, [0u8; 32]);
+        inputs
+    }
+
+    #[test]
+    fn test_disclosure_statement_accepts_historical_root() {
+        // Far older than any max_root_age_slots, but still in the window
+        let roots = roots_at(10, [0xaa; 32]);
+        let (total, commitments) =
+            disclosure_statement(&inputs(650, &[[1; 32], [2; 32]]), &roots, 1_000_000).unwrap();
+        assert_eq!(total, 650);
+        assert_eq!(commitments, vec![[1; 32], [2; 32]]);
+    }
+
+    #[test]
+    fn test_disclosure_statement_rejects_bad_inputs() {
+        let roots = roots_at(10, [0xaa; 32]);
+        let check = |inputs: &[[u8; 32]]| disclosure_statement(inputs, &roots, 20).unwrap_err();
+
+        let valid = inputs(1, &[[1; 32]]);
+        assert_eq!(
+            check(&valid[..DISCLOSURE_PUBLIC_INPUTS - 1]),
+            ZkPoolError::InvalidPublicInputCount.into()
+        );
+
+        // Root outside the window
+        let mut unknown_root = valid.clone();
+        unknown_root[0] = [0xcc; 32];
+        assert_eq!(check(&unknown_root), ZkPoolError::RootNotFound.into());
+
+        // Total beyond u64
+        let mut large_total = valid.clone();
+        large_total[2][8] = 1;
+        assert_eq!(check(&large_total), ZkPoolError::AmountTooLarge.into());
+
+        // Nothing disclosed
+        assert_eq!(
+            check(&inputs(0, &[])),
+            ZkPoolError::InvalidDisclosure.into()
+        );
+
+        // The same note counted twice, even around padding
+        assert_eq!(
+            check(&inputs(2, &[[1; 32], [0u8; 32], [1; 32]])),
+            ZkPoolError::InvalidDisclosure.into()
+        );
+    }
+}
@@ -0,0 +1,48 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::events::ProtocolFeesWithdrawn;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct WithdrawProtocolFees<'info> {
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED],
+        bump = fee_vault.bump,
+        has_one = fee_manager @ ZkPoolError::Unauthorized
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    /// CHECK: Any account chosen by the fee manager
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    pub fee_manager: Signer<'info>,
+}
+
+/// The vault is program-owned, so lamports are moved directly; its rent stays
+pub fn withdraw_protocol_fees(ctx: Context<WithdrawProtocolFees>, amount: u64) -> Result<()> {
+    let vault_info = ctx.accounts.fee_vault.to_account_info();
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(vault_info.data_len());
+    let available = vault_info.lamports().saturating_sub(rent_exempt_minimum);
+    require!(amount <= available, ZkPoolError::InsufficientProtocolFees);
+
+    **vault_info.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.destination.try_borrow_mut_lamports()? += amount;
+
+    let fee_vault = &mut ctx.accounts.fee_vault;
+    fee_vault.total_withdrawn = fee_vault
+        .total_withdrawn
+        .checked_add(amount)
+        .ok_or(ZkPoolError::ArithmeticOverflow)?;
+
+    emit!(ProtocolFeesWithdrawn {
+        amount,
+        destination: ctx.accounts.destination.key(),
+        fee_manager: ctx.accounts.fee_manager.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
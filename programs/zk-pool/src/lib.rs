@@ -1,13 +1,16 @@
 use anchor_lang::prelude::*;
 
 pub mod constants;
+pub mod ed25519;
 pub mod errors;
 pub mod events;
 pub mod instructions;
+pub mod migration;
 pub mod state;
 pub mod verifier;
 
 use instructions::*;
+use state::{PoolStats, ProposalAccountMeta};
 
 declare_id!("Hza5rjYmJnoYsjsgsuxLkyxLoWVo6RCUZxCB3x17v8qz");
 
@@ -25,6 +28,11 @@ pub mod zk_pool {
         instructions::initialize(ctx, merkle_depth, root_window, abi_hash)
     }
 
+    /// Create the VK account for a circuit added after initialize (admin only)
+    pub fn init_verification_key(ctx: Context<InitVerificationKey>, circuit: u8) -> Result<()> {
+        instructions::init_verification_key(ctx, circuit)
+    }
+
     /// Set or update verification key for a circuit (admin only)
     pub fn set_verification_key(
         ctx: Context<SetVerificationKey>,
@@ -40,9 +48,173 @@ pub mod zk_pool {
         instructions::add_root(ctx, root)
     }
 
-    /// Set pause state (admin only)
-    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
-        instructions::set_paused(ctx, paused)
+    /// Add a root signed off-chain by the configured root signer (any relayer)
+    pub fn add_signed_root(
+        ctx: Context<AddSignedRoot>,
+        root: [u8; 32],
+        sequence: u64,
+    ) -> Result<()> {
+        instructions::add_signed_root(ctx, root, sequence)
+    }
+
+    /// Set the Ed25519 key trusted to sign roots (admin only)
+    pub fn set_root_signer(ctx: Context<SetRootSigner>, root_signer: Pubkey) -> Result<()> {
+        instructions::set_root_signer(ctx, root_signer)
+    }
+
+    /// Set the curator allowed to add association set roots (admin only)
+    pub fn set_association_curator(
+        ctx: Context<SetAssociationCurator>,
+        curator: Pubkey,
+    ) -> Result<()> {
+        instructions::set_association_curator(ctx, curator)
+    }
+
+    /// Approve an association set root for unshield (admin or curator)
+    pub fn add_association_root(ctx: Context<AddAssociationRoot>, root: [u8; 32]) -> Result<()> {
+        instructions::add_association_root(ctx, root)
+    }
+
+    /// Set the compliance key allowed to edit the deny-list (admin only)
+    pub fn set_compliance_authority(
+        ctx: Context<SetComplianceAuthority>,
+        compliance: Pubkey,
+    ) -> Result<()> {
+        instructions::set_compliance_authority(ctx, compliance)
+    }
+
+    /// Add or remove an unshield recipient on the deny-list (admin or compliance)
+    pub fn set_recipient_denied(
+        ctx: Context<SetRecipientDenied>,
+        recipient: Pubkey,
+        denied: bool,
+    ) -> Result<()> {
+        instructions::set_recipient_denied(ctx, recipient, denied)
+    }
+
+    /// Set the credential issuer and toggle the deposit credential gate (admin only)
+    pub fn set_credential_policy(
+        ctx: Context<SetCredentialPolicy>,
+        issuer: Pubkey,
+        required: bool,
+    ) -> Result<()> {
+        instructions::set_credential_policy(ctx, issuer, required)
+    }
+
+    /// Issue or renew a depositor credential (issuer only)
+    pub fn issue_credential(
+        ctx: Context<IssueCredential>,
+        holder: Pubkey,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::issue_credential(ctx, holder, expires_at)
+    }
+
+    /// Revoke a depositor credential and refund its rent (issuer or admin)
+    pub fn revoke_credential(ctx: Context<RevokeCredential>) -> Result<()> {
+        instructions::revoke_credential(ctx)
+    }
+
+    /// Register root attesters and toggle attestation mode (admin only)
+    pub fn set_attesters(
+        ctx: Context<SetAttesters>,
+        attesters: Vec<Pubkey>,
+        threshold: u8,
+        expiry_slots: u64,
+    ) -> Result<()> {
+        instructions::set_attesters(ctx, attesters, threshold, expiry_slots)
+    }
+
+    /// Attest a root; it is added once the attester threshold is reached (attester only)
+    pub fn attest_root(ctx: Context<AttestRoot>, root: [u8; 32]) -> Result<()> {
+        instructions::attest_root(ctx, root)
+    }
+
+    /// Close an expired pending attestation and refund its rent
+    pub fn close_pending_root(ctx: Context<ClosePendingRoot>) -> Result<()> {
+        instructions::close_pending_root(ctx)
+    }
+
+    /// Revoke a stored root so spends against it are rejected (admin only)
+    pub fn revoke_root(ctx: Context<RevokeRoot>, root: [u8; 32]) -> Result<()> {
+        instructions::revoke_root(ctx, root)
+    }
+
+    /// Resize the root window, keeping the most recent roots (admin only)
+    pub fn set_root_window(ctx: Context<SetRootWindow>, root_window: u16) -> Result<()> {
+        instructions::set_root_window(ctx, root_window)
+    }
+
+    /// Set per-transaction shield/unshield limits and the TVL cap, 0 = unlimited (admin only)
+    pub fn set_limits(
+        ctx: Context<SetLimits>,
+        max_shield_amount: u64,
+        max_unshield_amount: u64,
+        tvl_cap: u64,
+    ) -> Result<()> {
+        instructions::set_limits(ctx, max_shield_amount, max_unshield_amount, tvl_cap)
+    }
+
+    /// Set the unshield amount above which payouts are delayed, the delay and the guardian (admin only)
+    pub fn set_withdrawal_delay(
+        ctx: Context<SetWithdrawalDelay>,
+        threshold: u64,
+        delay_slots: u64,
+        guardian: Pubkey,
+    ) -> Result<()> {
+        instructions::set_withdrawal_delay(ctx, threshold, delay_slots, guardian)
+    }
+
+    /// Require every shield and unshield to move exactly `denomination`, 0 = any amount (admin only)
+    pub fn set_denomination(ctx: Context<SetDenomination>, denomination: u64) -> Result<()> {
+        instructions::set_denomination(ctx, denomination)
+    }
+
+    /// Set the shield/unshield protocol fees in bps and the fee manager (admin only)
+    pub fn set_protocol_fees(
+        ctx: Context<SetProtocolFees>,
+        shield_fee_bps: u16,
+        unshield_fee_bps: u16,
+        fee_manager: Pubkey,
+    ) -> Result<()> {
+        instructions::set_protocol_fees(ctx, shield_fee_bps, unshield_fee_bps, fee_manager)
+    }
+
+    /// Withdraw collected protocol fees from the fee vault (fee manager only)
+    pub fn withdraw_protocol_fees(ctx: Context<WithdrawProtocolFees>, amount: u64) -> Result<()> {
+        instructions::withdraw_protocol_fees(ctx, amount)
+    }
+
+    /// Let unshield fees top up new recipients to the rent-exempt minimum (admin only)
+    pub fn set_recipient_top_up(ctx: Context<SetRecipientTopUp>, enabled: bool) -> Result<()> {
+        instructions::set_recipient_top_up(ctx, enabled)
+    }
+
+    /// Limit treasury outflow to `max_outflow` per `window_slots`, 0 = unlimited (admin only)
+    pub fn set_withdrawal_rate_limit(
+        ctx: Context<SetWithdrawalRateLimit>,
+        max_outflow: u64,
+        window_slots: u64,
+    ) -> Result<()> {
+        instructions::set_withdrawal_rate_limit(ctx, max_outflow, window_slots)
+    }
+
+    /// Set the maximum root age in slots accepted by spends (admin only)
+    pub fn set_max_root_age(ctx: Context<SetMaxRootAge>, max_root_age_slots: u64) -> Result<()> {
+        instructions::set_max_root_age(ctx, max_root_age_slots)
+    }
+
+    /// Pause or unpause the flows selected by `circuit_mask` (admin only)
+    pub fn set_paused(ctx: Context<SetPaused>, circuit_mask: u8, paused: bool) -> Result<()> {
+        instructions::set_paused(ctx, circuit_mask, paused)
+    }
+
+    /// Set how long unshield may stay paused before it reopens (admin only)
+    pub fn set_max_pause_duration(
+        ctx: Context<SetMaxPauseDuration>,
+        max_pause_duration: i64,
+    ) -> Result<()> {
+        instructions::set_max_pause_duration(ctx, max_pause_duration)
     }
 
     /// Submit a shield proof (deposit into shielded pool)
@@ -50,25 +222,160 @@ pub mod zk_pool {
         ctx: Context<SubmitShield>,
         proof: Vec<u8>,
         public_inputs: Vec<[u8; 32]>,
+        encrypted_output: Vec<u8>,
     ) -> Result<()> {
-        instructions::submit_shield(ctx, proof, public_inputs)
+        instructions::submit_shield(ctx, proof, public_inputs, encrypted_output)
     }
 
-    /// Submit a transfer proof (private transfer within pool)
+    /// Submit a transfer proof against a root of tree `tree_index`
     pub fn submit_transfer(
         ctx: Context<SubmitTransfer>,
         proof: Vec<u8>,
         public_inputs: Vec<[u8; 32]>,
+        tree_index: u32,
+        encrypted_output: Vec<u8>,
     ) -> Result<()> {
-        instructions::submit_transfer(ctx, proof, public_inputs)
+        instructions::submit_transfer(ctx, proof, public_inputs, tree_index, encrypted_output)
     }
 
-    /// Submit an unshield proof (withdrawal from pool)
+    /// Submit an unshield proof against a root of tree `tree_index`
     pub fn submit_unshield(
         ctx: Context<SubmitUnshield>,
         proof: Vec<u8>,
         public_inputs: Vec<[u8; 32]>,
+        tree_index: u32,
+    ) -> Result<()> {
+        instructions::submit_unshield(ctx, proof, public_inputs, tree_index)
+    }
+
+    /// Submit an unshield proof, then invoke the program its call hash commits to
+    pub fn submit_unshield_and_call<'info>(
+        ctx: Context<'_, '_, '_, 'info, SubmitUnshieldAndCall<'info>>,
+        proof: Vec<u8>,
+        public_inputs: Vec<[u8; 32]>,
+        tree_index: u32,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::submit_unshield_and_call(ctx, proof, public_inputs, tree_index, data)
+    }
+
+    /// Start solvency tracking for a pool created before PoolState (admin only)
+    pub fn init_pool_state(ctx: Context<InitPoolState>) -> Result<()> {
+        instructions::init_pool_state(ctx)
+    }
+
+    /// Return deposit, withdrawal and fee totals and the treasury balance (read-only)
+    pub fn get_pool_stats(ctx: Context<GetPoolStats>) -> Result<PoolStats> {
+        instructions::get_pool_stats(ctx)
+    }
+
+    /// Submit an unshield proof whose payout waits for the withdrawal delay
+    pub fn submit_unshield_delayed(
+        ctx: Context<SubmitUnshieldDelayed>,
+        proof: Vec<u8>,
+        public_inputs: Vec<[u8; 32]>,
+        tree_index: u32,
+    ) -> Result<()> {
+        instructions::submit_unshield_delayed(ctx, proof, public_inputs, tree_index)
+    }
+
+    /// Pay out a queued withdrawal once its delay has elapsed
+    pub fn claim_withdrawal(ctx: Context<ClaimWithdrawal>) -> Result<()> {
+        instructions::claim_withdrawal(ctx)
+    }
+
+    /// Cancel a queued withdrawal, keeping its nullifier spent (guardian or admin)
+    pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>) -> Result<()> {
+        instructions::cancel_withdrawal(ctx)
+    }
+
+    /// Append a batch of queued commitments, proven by the batch_append circuit
+    pub fn batch_append(
+        ctx: Context<BatchAppend>,
+        proof: Vec<u8>,
+        new_root: [u8; 32],
+    ) -> Result<()> {
+        instructions::batch_append(ctx, proof, new_root)
+    }
+
+    /// Verify a disclosure proof over notes of tree `tree_index` (read-only)
+    pub fn verify_disclosure(
+        ctx: Context<VerifyDisclosure>,
+        proof: Vec<u8>,
+        public_inputs: Vec<[u8; 32]>,
+        tree_index: u32,
+    ) -> Result<()> {
+        instructions::verify_disclosure(ctx, proof, public_inputs, tree_index)
+    }
+
+    /// Start a new commitment tree (anyone once the active tree is full, admin anytime)
+    pub fn rollover_tree(ctx: Context<RolloverTree>) -> Result<()> {
+        instructions::rollover_tree(ctx)
+    }
+
+    /// Upgrade a pre-versioning PoolConfig to the current layout (admin only)
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        instructions::migrate_config(ctx)
+    }
+
+    /// Upgrade a pre-versioning RootsAccount and seed its queue from the legacy tree (admin only)
+    pub fn migrate_roots(
+        ctx: Context<MigrateRoots>,
+        legacy_root: [u8; 32],
+        leaf_count: u64,
+    ) -> Result<()> {
+        instructions::migrate_roots(ctx, legacy_root, leaf_count)
+    }
+
+    /// Upgrade a pre-versioning NullifiersAccount shard to the current layout (admin only)
+    pub fn migrate_nullifiers(ctx: Context<MigrateNullifiers>, shard: u16) -> Result<()> {
+        instructions::migrate_nullifiers(ctx, shard)
+    }
+
+    /// Upgrade a pre-versioning VerificationKeyAccount to the current layout (admin only)
+    pub fn migrate_verification_key(
+        ctx: Context<MigrateVerificationKey>,
+        circuit: u8,
+    ) -> Result<()> {
+        instructions::migrate_verification_key(ctx, circuit)
+    }
+
+    /// Create the built-in admin multisig and hand it admin rights (admin only)
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::create_multisig(ctx, signers, threshold)
+    }
+
+    /// Propose an admin instruction for multisig approval (multisig signer only)
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        accounts: Vec<ProposalAccountMeta>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::create_proposal(ctx, accounts, data)
+    }
+
+    /// Approve a pending proposal (multisig signer only)
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        instructions::approve_proposal(ctx)
+    }
+
+    /// Execute a proposal that reached the approval threshold
+    pub fn execute_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteProposal<'info>>,
+    ) -> Result<()> {
+        instructions::execute_proposal(ctx)
+    }
+
+    /// Replace the multisig signer set and threshold (multisig PDA only)
+    pub fn set_multisig_signers(
+        ctx: Context<SetMultisigSigners>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
     ) -> Result<()> {
-        instructions::submit_unshield(ctx, proof, public_inputs)
+        instructions::set_multisig_signers(ctx, signers, threshold)
     }
 }
@@ -0,0 +1,486 @@
+use crate::constants::*;
+use crate::errors::ZkPoolError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+// ============================================================================
+// ACCOUNT MIGRATIONS
+// ============================================================================
+//
+// Accounts created before versioning (version 0) carry no version byte and no
+// reserved padding, so they cannot be deserialized as the current structs.
+// The legacy layouts are kept here verbatim; each migrate_* instruction
+// decodes one, upgrades it to the current layout, reallocs the account and
+// rewrites it in place.
+//
+// Version 0 accounts are recognized by their exact allocated size, which the
+// upgraded (larger) layouts never match.
+//
+// ============================================================================
+
+/// PoolConfig layout before versioning (version 0)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PoolConfigV0 {
+    pub admin: Pubkey,
+    pub merkle_depth: u8,
+    pub root_window: u16,
+    pub abi_hash: [u8; 32],
+    pub vk_hashes: VkHashes,
+    pub paused: bool,
+    pub bump: u8,
+}
+
+impl PoolConfigV0 {
+    pub const LEN: usize = 8 + 32 + 1 + 2 + 32 + 96 + 1 + 1;
+}
+
+/// RootsAccount layout before versioning (version 0)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RootsAccountV0 {
+    pub roots: Vec<[u8; 32]>,
+    pub cursor: u16,
+    pub size: u16,
+    pub capacity: u16,
+    pub bump: u8,
+}
+
+impl RootsAccountV0 {
+    pub fn space_for(capacity: u16) -> usize {
+        8 + 4 + 2 + 2 + 2 + 1 + (capacity as usize * 32)
+    }
+}
+
+/// NullifiersAccount layout before versioning (version 0)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct NullifiersAccountV0 {
+    pub shard: u16,
+    pub nullifiers: Vec<[u8; 32]>,
+    pub bump: u8,
+}
+
+impl NullifiersAccountV0 {
+    pub fn space_for(capacity: usize) -> usize {
+        8 + 2 + 4 + 1 + (capacity * 32)
+    }
+}
+
+/// VerificationKeyAccount layout before versioning (version 0)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VerificationKeyAccountV0 {
+    pub circuit: u8,
+    pub n_public: u32,
+    pub vk_data: Vec<u8>,
+    pub vk_hash: [u8; 32],
+    pub bump: u8,
+}
+
+impl VerificationKeyAccountV0 {
+    pub fn space_for(n_public: u32) -> usize {
+        8 + 1 + 4 + 4 + 32 + 1 + VerificationKeyAccount::vk_data_len(n_public)
+    }
+}
+
+/// Decode a version 0 PoolConfig and upgrade it to the current layout
+pub fn upgrade_config(data: &[u8], now: i64) -> Result<PoolConfig> {
+    require!(
+        data.len() == PoolConfigV0::LEN,
+        ZkPoolError::InvalidAccountVersion
+    );
+    let old: PoolConfigV0 = decode_v0(data, PoolConfig::DISCRIMINATOR)?;
+
+    // The legacy flag paused every flow; start the escape-hatch clock now
+    Ok(PoolConfig {
+        version: ACCOUNT_VERSION,
+        admin: old.admin,
+        merkle_depth: old.merkle_depth,
+        root_window: old.root_window,
+        abi_hash: old.abi_hash,
+        vk_hashes: old.vk_hashes,
+        paused_mask: if old.paused { PAUSE_ALL } else { 0 },
+        paused_at: if old.paused { now } else { 0 },
+        max_pause_duration: DEFAULT_MAX_PAUSE_DURATION,
+        bump: old.bump,
+        max_root_age_slots: 0,
+        root_attestation: false,
+        root_signer: Pubkey::default(),
+        active_tree: 0,
+        batch_append_vk_hash: [0u8; 32],
+        disclosure_vk_hash: [0u8; 32],
+        require_credential: false,
+        credential_issuer: Pubkey::default(),
+        max_shield_amount: 0,
+        max_unshield_amount: 0,
+        tvl_cap: 0,
+        tvl: 0,
+        withdrawal_delay_threshold: 0,
+        withdrawal_delay_slots: 0,
+        guardian: Pubkey::default(),
+        top_up_recipients: false,
+        shield_fee_bps: 0,
+        unshield_fee_bps: 0,
+        denomination: 0,
+        reserved: [0u8; CONFIG_RESERVED_LEN],
+    })
+}
+
+/// Decode a version 0 RootsAccount and upgrade it to the current layout
+///
+/// Legacy roots carry no metadata; they are stamped with the migration
+/// slot/time (so in-flight proofs stay valid under a max age) and numbered
+/// oldest-first.
+pub fn upgrade_roots(data: &[u8], slot: u64, timestamp: i64) -> Result<RootsAccount> {
+    let old: RootsAccountV0 = decode_v0(data, RootsAccount::DISCRIMINATOR)?;
+    require!(
+        data.len() == RootsAccountV0::space_for(old.capacity)
+            && old.capacity > 0
+            && old.roots.len() == old.capacity as usize,
+        ZkPoolError::InvalidAccountVersion
+    );
+
+    let capacity = old.capacity as usize;
+    let oldest = (old.cursor as usize + capacity - old.size as usize) % capacity;
+    let mut roots = vec![RootEntry::default(); capacity];
+    for (seq, offset) in (0..old.size as usize).enumerate() {
+        let idx = (oldest + offset) % capacity;
+        roots[idx] = RootEntry {
+            root: old.roots[idx],
+            slot,
+            timestamp,
+            seq: seq as u64,
+            revoked: false,
+        };
+    }
+
+    Ok(RootsAccount {
+        version: ACCOUNT_VERSION,
+        roots,
+        cursor: old.cursor,
+        size: old.size,
+        capacity: old.capacity,
+        next_seq: old.size as u64,
+        bump: old.bump,
+        // Legacy pools only had one tree; its leaf count is set by migrate_roots
+        tree_index: 0,
+        leaf_count: 0,
+        reserved: [0u8; ROOTS_RESERVED_LEN],
+    })
+}
+
+/// Where queued insertion resumes on a migrated legacy tree
+///
+/// The legacy program never tracked leaves on-chain, so the admin passes the
+/// tree's leaf count and the root covering those leaves, which must be the
+/// newest stored root. Batches fill whole `BATCH_APPEND_SIZE` subtrees, so the
+/// count is rounded up and the skipped leaves stay empty. No frontier is stored:
+/// the batch prover rebuilds it from the legacy leaves, and the circuit proves
+/// the target subtree is empty under `legacy_root`, so a count that is too low
+/// cannot overwrite legacy notes.
+///
+/// Returns the queue's `(current_root, next_index)`.
+pub fn legacy_queue_start(
+    roots: &RootsAccount,
+    legacy_root: [u8; 32],
+    leaf_count: u64,
+    merkle_depth: u8,
+) -> Result<([u8; 32], u64)> {
+    let batch = BATCH_APPEND_SIZE as u64;
+    let next_index = leaf_count
+        .div_ceil(batch)
+        .checked_mul(batch)
+        .ok_or(ZkPoolError::ArithmeticOverflow)?;
+    require!(
+        next_index <= 1u64 << merkle_depth,
+        ZkPoolError::LegacyTreeMismatch
+    );
+
+    match roots.latest() {
+        Some(latest) => require!(latest.root == legacy_root, ZkPoolError::LegacyTreeMismatch),
+        // A tree without roots can only be empty; zero marks it as such for the circuit
+        None => require!(
+            leaf_count == 0 && legacy_root == [0u8; 32],
+            ZkPoolError::LegacyTreeMismatch
+        ),
+    }
+
+    Ok((legacy_root, next_index))
+}
+
+/// Decode a version 0 NullifiersAccount and upgrade it to the current layout
+pub fn upgrade_nullifiers(data: &[u8]) -> Result<NullifiersAccount> {
+    require!(
+        data.len() == NullifiersAccountV0::space_for(NULLIFIER_SHARD_SIZE),
+        ZkPoolError::InvalidAccountVersion
+    );
+    let old: NullifiersAccountV0 = decode_v0(data, NullifiersAccount::DISCRIMINATOR)?;
+
+    Ok(NullifiersAccount {
+        version: ACCOUNT_VERSION,
+        shard: old.shard,
+        nullifiers: old.nullifiers,
+        bump: old.bump,
+        reserved: [0u8; NULLIFIERS_RESERVED_LEN],
+    })
+}
+
+/// Decode a version 0 VerificationKeyAccount and upgrade it to the current layout
+pub fn upgrade_verification_key(data: &[u8]) -> Result<VerificationKeyAccount> {
+    let old: VerificationKeyAccountV0 = decode_v0(data, VerificationKeyAccount::DISCRIMINATOR)?;
+    require!(
+        data.len() == VerificationKeyAccountV0::space_for(old.n_public),
+        ZkPoolError::InvalidAccountVersion
+    );
+
+    Ok(VerificationKeyAccount {
+        version: ACCOUNT_VERSION,
+        circuit: old.circuit,
+        n_public: old.n_public,
+        vk_data: old.vk_data,
+        vk_hash: old.vk_hash,
+        bump: old.bump,
+        reserved: [0u8; VK_RESERVED_LEN],
+    })
+}
+
+/// Check the discriminator and decode a legacy account body
+fn decode_v0<T: AnchorDeserialize>(data: &[u8], discriminator: &[u8]) -> Result<T> {
+    require!(
+        data.len() >= 8 && &data[..8] == discriminator,
+        ZkPoolError::InvalidAccountVersion
+    );
+
+    T::deserialize(&mut &data[8..]).map_err(|_| ZkPoolError::InvalidAccountVersion.into())
+}
+
+/// Grow a program-owned account to `new_len`, topping up rent from `payer`,
+/// and write the upgraded account (discriminator included) in place
+pub fn rewrite_account<'info, T: AccountSerialize>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    upgraded: &T,
+    new_len: usize,
+) -> Result<()> {
+    require!(
+        account.owner == &crate::ID,
+        ZkPoolError::InvalidAccountVersion
+    );
+
+    let required = Rent::get()?.minimum_balance(new_len);
+    let shortfall = required.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        let cpi_context = CpiContext::new(
+            system_program.clone(),
+            anchor_lang::system_program::Transfer {
+                from: payer.clone(),
+                to: account.clone(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, shortfall)?;
+    }
+
+    account.resize(new_len)?;
+
+    let mut data = account.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    upgraded.try_serialize(&mut writer)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn legacy_bytes<T: AnchorSerialize>(discriminator: &[u8], body: &T, len: usize) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        body.serialize(&mut data).unwrap();
+        data.resize(len, 0);
+        data
+    }
+
+    fn serialized_len<T: AccountSerialize>(account: &T) -> usize {
+        let mut out = Vec::new();
+        account.try_serialize(&mut out).unwrap();
+        out.len()
+    }
+
+    #[test]
+    fn test_upgrade_config_from_v0() {
+        let old = PoolConfigV0 {
+            admin: Pubkey::new_unique(),
+            merkle_depth: 20,
+            root_window: 64,
+            abi_hash: [7u8; 32],
+            vk_hashes: VkHashes {
+                shield: [1u8; 32],
+                transfer: [2u8; 32],
+                unshield: [3u8; 32],
+            },
+            paused: true,
+            bump: 254,
+        };
+        let data = legacy_bytes(PoolConfig::DISCRIMINATOR, &old, PoolConfigV0::LEN);
+
+        let upgraded = upgrade_config(&data, 1_000).unwrap();
+
+        assert_eq!(upgraded.version, ACCOUNT_VERSION);
+        assert_eq!(upgraded.admin, old.admin);
+        assert_eq!(upgraded.root_window, 64);
+        assert_eq!(upgraded.vk_hashes.unshield, [3u8; 32]);
+        assert_eq!(upgraded.paused_mask, PAUSE_ALL);
+        assert_eq!(upgraded.paused_at, 1_000);
+        assert_eq!(upgraded.bump, 254);
+        assert_eq!(serialized_len(&upgraded), PoolConfig::LEN);
+
+        // An upgraded account is not mistaken for a legacy one
+        let mut current = Vec::new();
+        upgraded.try_serialize(&mut current).unwrap();
+        assert!(upgrade_config(&current, 1_000).is_err());
+    }
+
+    #[test]
+    fn test_upgrade_roots_from_v0() {
+        let mut roots = vec![[0u8; 32]; 4];
+        roots[0] = [9u8; 32];
+        roots[1] = [8u8; 32];
+        let old = RootsAccountV0 {
+            roots,
+            cursor: 2,
+            size: 2,
+            capacity: 4,
+            bump: 253,
+        };
+        let data = legacy_bytes(
+            RootsAccount::DISCRIMINATOR,
+            &old,
+            RootsAccountV0::space_for(4),
+        );
+
+        let upgraded = upgrade_roots(&data, 500, 1_000).unwrap();
+
+        assert_eq!(upgraded.version, ACCOUNT_VERSION);
+        assert!(upgraded.contains_root(&[9u8; 32], 500, 1));
+        assert!(upgraded.contains_root(&[8u8; 32], 500, 1));
+        assert_eq!(upgraded.roots[1].seq, 1);
+        assert_eq!(upgraded.next_seq, 2);
+        assert_eq!(upgraded.cursor, 2);
+        assert_eq!(upgraded.capacity, 4);
+        assert_eq!(serialized_len(&upgraded), RootsAccount::space_for(4));
+
+        // Wrong discriminator is rejected
+        let data = legacy_bytes(
+            PoolConfig::DISCRIMINATOR,
+            &old,
+            RootsAccountV0::space_for(4),
+        );
+        assert!(upgrade_roots(&data, 500, 1_000).is_err());
+    }
+
+    #[test]
+    fn test_legacy_queue_start_from_non_empty_tree() {
+        let old = RootsAccountV0 {
+            roots: vec![[9u8; 32], [8u8; 32], [0u8; 32], [0u8; 32]],
+            cursor: 2,
+            size: 2,
+            capacity: 4,
+            bump: 253,
+        };
+        let data = legacy_bytes(
+            RootsAccount::DISCRIMINATOR,
+            &old,
+            RootsAccountV0::space_for(4),
+        );
+        let upgraded = upgrade_roots(&data, 500, 1_000).unwrap();
+
+        // 37 legacy leaves: the queue continues from the newest root at the next batch boundary
+        let (root, next_index) = legacy_queue_start(&upgraded, [8u8; 32], 37, 20).unwrap();
+        assert_eq!(root, [8u8; 32]);
+        assert_eq!(next_index, 48);
+        assert_eq!(
+            legacy_queue_start(&upgraded, [8u8; 32], 48, 20).unwrap(),
+            ([8u8; 32], 48)
+        );
+
+        // The root must be the newest one, never zero (the empty tree) or an older root
+        assert_eq!(
+            legacy_queue_start(&upgraded, [0u8; 32], 37, 20).unwrap_err(),
+            ZkPoolError::LegacyTreeMismatch.into()
+        );
+        assert_eq!(
+            legacy_queue_start(&upgraded, [9u8; 32], 37, 20).unwrap_err(),
+            ZkPoolError::LegacyTreeMismatch.into()
+        );
+
+        // The count must fit the tree after rounding up
+        assert_eq!(
+            legacy_queue_start(&upgraded, [8u8; 32], 17, 4).unwrap_err(),
+            ZkPoolError::LegacyTreeMismatch.into()
+        );
+
+        // A tree without roots is only accepted as empty
+        let empty = RootsAccountV0 {
+            roots: vec![[0u8; 32]; 4],
+            cursor: 0,
+            size: 0,
+            capacity: 4,
+            bump: 253,
+        };
+        let data = legacy_bytes(
+            RootsAccount::DISCRIMINATOR,
+            &empty,
+            RootsAccountV0::space_for(4),
+        );
+        let upgraded = upgrade_roots(&data, 500, 1_000).unwrap();
+        assert_eq!(
+            legacy_queue_start(&upgraded, [0u8; 32], 0, 20).unwrap(),
+            ([0u8; 32], 0)
+        );
+        assert_eq!(
+            legacy_queue_start(&upgraded, [0u8; 32], 5, 20).unwrap_err(),
+            ZkPoolError::LegacyTreeMismatch.into()
+        );
+    }
+
+    #[test]
+    fn test_upgrade_nullifiers_and_vk_from_v0() {
+        let old = NullifiersAccountV0 {
+            shard: 0,
+            nullifiers: vec![[5u8; 32]],
+            bump: 252,
+        };
+        let data = legacy_bytes(
+            NullifiersAccount::DISCRIMINATOR,
+            &old,
+            NullifiersAccountV0::space_for(NULLIFIER_SHARD_SIZE),
+        );
+
+        let upgraded = upgrade_nullifiers(&data).unwrap();
+        assert_eq!(upgraded.version, ACCOUNT_VERSION);
+        assert!(upgraded.is_spent(&[5u8; 32]));
+        assert!(serialized_len(&upgraded) <= NullifiersAccount::space_for(NULLIFIER_SHARD_SIZE));
+
+        let n_public = SHIELD_PUBLIC_INPUTS as u32;
+        let old = VerificationKeyAccountV0 {
+            circuit: CIRCUIT_SHIELD,
+            n_public,
+            vk_data: vec![1u8; VerificationKeyAccount::vk_data_len(n_public)],
+            vk_hash: [4u8; 32],
+            bump: 251,
+        };
+        let data = legacy_bytes(
+            VerificationKeyAccount::DISCRIMINATOR,
+            &old,
+            VerificationKeyAccountV0::space_for(n_public),
+        );
+
+        let upgraded = upgrade_verification_key(&data).unwrap();
+        assert_eq!(upgraded.version, ACCOUNT_VERSION);
+        assert_eq!(upgraded.vk_hash, [4u8; 32]);
+        assert_eq!(upgraded.vk_data.len(), old.vk_data.len());
+        assert_eq!(
+            serialized_len(&upgraded),
+            VerificationKeyAccount::space_for(n_public)
+        );
+    }
+}
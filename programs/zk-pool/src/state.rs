@@ -1,16 +1,29 @@
+use crate::constants::{
+    ASSOCIATION_RESERVED_LEN, ATTESTERS_RESERVED_LEN, CONFIG_RESERVED_LEN, CREDENTIAL_RESERVED_LEN,
+    DENY_LIST_RESERVED_LEN, FEE_VAULT_RESERVED_LEN, MAX_ROOT_WINDOW, MULTISIG_RESERVED_LEN,
+    NULLIFIERS_RESERVED_LEN, PENDING_ROOT_RESERVED_LEN, PENDING_WITHDRAWAL_RESERVED_LEN,
+    POOL_STATE_RESERVED_LEN, PROPOSAL_RESERVED_LEN, QUEUE_RESERVED_LEN, ROOTS_RESERVED_LEN,
+    TREASURY_STATE_RESERVED_LEN, VK_RESERVED_LEN,
+};
 use crate::errors::ZkPoolError;
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use anchor_lang::solana_program::instruction::AccountMeta;
 
 /// Main pool configuration
 #[account]
 pub struct PoolConfig {
+    /// Account layout version (ACCOUNT_VERSION)
+    pub version: u8,
+
     /// Administrator public key
     pub admin: Pubkey,
 
     /// Merkle tree depth (1-32)
     pub merkle_depth: u8,
 
-    /// Number of recent roots to keep
+    /// Number of recent roots kept by trees created from now on
+    /// (each RootsAccount keeps its own capacity)
     pub root_window: u16,
 
     /// Hash of the ABI specification (for versioning)
@@ -19,22 +32,284 @@ pub struct PoolConfig {
     /// Verification key hashes for each circuit
     pub vk_hashes: VkHashes,
 
-    /// Emergency pause flag (gates all submit_* operations)
-    pub paused: bool,
+    /// Emergency pause bitmask (one PAUSE_* bit per circuit)
+    pub paused_mask: u8,
 
     /// PDA bump
     pub bump: u8,
+
+    /// Unix timestamp when unshield was paused (0 when not paused)
+    pub paused_at: i64,
+
+    /// Seconds after which a paused unshield reopens (0 disables the escape hatch)
+    pub max_pause_duration: i64,
+
+    /// Maximum root age in slots accepted by spends (0 disables the limit)
+    pub max_root_age_slots: u64,
+
+    /// Attestation mode: roots enter only through attest_root quorum
+    pub root_attestation: bool,
+
+    /// Ed25519 key whose signed roots add_signed_root accepts (default disables)
+    pub root_signer: Pubkey,
+
+    /// Index of the commitment tree receiving new leaves
+    pub active_tree: u32,
+
+    /// Verification key hash of the batch_append circuit
+    pub batch_append_vk_hash: [u8; 32],
+
+    /// Verification key hash of the disclosure circuit
+    pub disclosure_vk_hash: [u8; 32],
+
+    /// Permissioned mode: submit_shield requires a valid depositor credential
+    pub require_credential: bool,
+
+    /// Key allowed to issue depositor credentials (default = none)
+    pub credential_issuer: Pubkey,
+
+    /// Maximum amount per shield (0 = unlimited)
+    pub max_shield_amount: u64,
+
+    /// Maximum amount per unshield (0 = unlimited)
+    pub max_unshield_amount: u64,
+
+    /// Maximum value held by the pool (0 = unlimited)
+    pub tvl_cap: u64,
+
+    /// Value shielded minus value unshielded since limits were introduced
+    pub tvl: u64,
+
+    /// Unshields above this amount must use the delayed queue (0 disables)
+    pub withdrawal_delay_threshold: u64,
+
+    /// Slots a queued withdrawal waits before it can be claimed
+    pub withdrawal_delay_slots: u64,
+
+    /// Key allowed to cancel queued withdrawals besides the admin (default = none)
+    pub guardian: Pubkey,
+
+    /// Top up new recipients to the rent-exempt minimum out of the unshield fee
+    pub top_up_recipients: bool,
+
+    /// Protocol fee charged on top of each shield, in basis points
+    pub shield_fee_bps: u16,
+
+    /// Protocol fee taken out of each unshield, in basis points
+    pub unshield_fee_bps: u16,
+
+    /// Fixed amount every shield and unshield must move (0 = any amount)
+    pub denomination: u64,
+
+    /// Zeroed space for future fields
+    pub reserved: [u8; CONFIG_RESERVED_LEN],
 }
 
 impl PoolConfig {
     pub const LEN: usize = 8 + // discriminator
+        1 +  // version
         32 + // admin
         1 +  // merkle_depth
         2 +  // root_window
         32 + // abi_hash
         96 + // vk_hashes (3 * 32)
-        1 +  // paused
-        1; // bump
+        1 +  // paused_mask
+        1 +  // bump
+        8 +  // paused_at
+        8 +  // max_pause_duration
+        8 +  // max_root_age_slots
+        1 +  // root_attestation
+        32 + // root_signer
+        4 +  // active_tree
+        32 + // batch_append_vk_hash
+        32 + // disclosure_vk_hash
+        1 +  // require_credential
+        32 + // credential_issuer
+        8 +  // max_shield_amount
+        8 +  // max_unshield_amount
+        8 +  // tvl_cap
+        8 +  // tvl
+        8 +  // withdrawal_delay_threshold
+        8 +  // withdrawal_delay_slots
+        32 + // guardian
+        1 +  // top_up_recipients
+        2 +  // shield_fee_bps
+        2 +  // unshield_fee_bps
+        8 +  // denomination
+        CONFIG_RESERVED_LEN; // reserved
+
+    /// Check if submissions for a circuit are paused
+    pub fn is_paused(&self, circuit: u8) -> bool {
+        self.paused_mask & (1 << circuit) != 0
+    }
+
+    /// Set or clear the pause bits in `circuit_mask`, returning the bits that changed
+    ///
+    /// The escape-hatch clock starts only when unshield transitions to paused,
+    /// so re-pausing an already paused flow cannot extend the deadline.
+    pub fn set_paused(&mut self, circuit_mask: u8, paused: bool, now: i64) -> Result<u8> {
+        use crate::constants::{PAUSE_ALL, PAUSE_UNSHIELD};
+
+        // PAUSE_ALL stops every flow at once
+        require!(
+            circuit_mask != 0 && circuit_mask & !PAUSE_ALL == 0,
+            ZkPoolError::InvalidCircuitMask
+        );
+
+        let previous = self.paused_mask;
+        self.paused_mask = if paused {
+            previous | circuit_mask
+        } else {
+            previous & !circuit_mask
+        };
+
+        let changed = previous ^ self.paused_mask;
+        if changed & PAUSE_UNSHIELD != 0 {
+            self.paused_at = if paused { now } else { 0 };
+        }
+
+        Ok(changed)
+    }
+
+    /// Check if unshield is paused at `now`, honoring the escape hatch:
+    /// once the pause outlasts `max_pause_duration`, withdrawals reopen
+    pub fn is_unshield_paused(&self, now: i64) -> bool {
+        use crate::constants::CIRCUIT_UNSHIELD;
+
+        if !self.is_paused(CIRCUIT_UNSHIELD) {
+            return false;
+        }
+
+        self.max_pause_duration == 0 || now.saturating_sub(self.paused_at) < self.max_pause_duration
+    }
+
+    /// Index of the tree that rollover_tree creates next
+    pub fn next_tree(&self) -> Result<u32> {
+        self.active_tree
+            .checked_add(1)
+            .ok_or(ZkPoolError::ArithmeticOverflow.into())
+    }
+
+    /// Check `amount` matches the pool denomination in fixed-denomination mode
+    pub fn check_denomination(&self, amount: u64) -> Result<()> {
+        require!(
+            self.denomination == 0 || amount == self.denomination,
+            ZkPoolError::InvalidDenomination
+        );
+        Ok(())
+    }
+
+    /// Record a deposit, enforcing the denomination, the per-shield limit and the TVL cap
+    pub fn record_shield(&mut self, amount: u64) -> Result<()> {
+        self.check_denomination(amount)?;
+        require!(
+            self.max_shield_amount == 0 || amount <= self.max_shield_amount,
+            ZkPoolError::ShieldLimitExceeded
+        );
+
+        let tvl = self
+            .tvl
+            .checked_add(amount)
+            .ok_or(ZkPoolError::ArithmeticOverflow)?;
+        require!(
+            self.tvl_cap == 0 || tvl <= self.tvl_cap,
+            ZkPoolError::TvlCapExceeded
+        );
+
+        self.tvl = tvl;
+        Ok(())
+    }
+
+    /// Record a withdrawal, enforcing the denomination and the per-unshield limit
+    ///
+    /// Like `PoolState::expected_balance`, the counter never floors: a pool
+    /// cannot pay out more than it was counted as holding.
+    pub fn record_unshield(&mut self, amount: u64) -> Result<()> {
+        self.check_denomination(amount)?;
+        require!(
+            self.max_unshield_amount == 0 || amount <= self.max_unshield_amount,
+            ZkPoolError::UnshieldLimitExceeded
+        );
+
+        self.tvl = self
+            .tvl
+            .checked_sub(amount)
+            .ok_or(ZkPoolError::TreasuryInsolvent)?;
+        Ok(())
+    }
+
+    /// Check if an unshield of `amount` must wait in the withdrawal queue
+    pub fn requires_withdrawal_delay(&self, amount: u64) -> bool {
+        self.withdrawal_delay_threshold != 0 && amount > self.withdrawal_delay_threshold
+    }
+
+    /// Protocol fee owed on top of a shield of `amount`
+    pub fn shield_protocol_fee(&self, amount: u64) -> u64 {
+        bps_of(amount, self.shield_fee_bps)
+    }
+
+    /// Protocol fee taken out of an unshield of `amount`
+    pub fn unshield_protocol_fee(&self, amount: u64) -> u64 {
+        bps_of(amount, self.unshield_fee_bps)
+    }
+
+    /// Split an unshield into the recipient payout, the protocol fee and the
+    /// fee the treasury keeps
+    ///
+    /// A payout that would leave the recipient funded but below
+    /// `recipient_minimum` fails at runtime, so it is rejected up front; with
+    /// `top_up_recipients` the shortfall is first taken out of the fee.
+    pub fn plan_payout(
+        &self,
+        amount: u64,
+        fee: u64,
+        recipient_balance: u64,
+        recipient_minimum: u64,
+    ) -> Result<Payout> {
+        let protocol_fee = self.unshield_protocol_fee(amount);
+        let mut payout = Payout {
+            paid: amount
+                .checked_sub(fee)
+                .and_then(|rest| rest.checked_sub(protocol_fee))
+                .ok_or(ZkPoolError::FeeExceedsAmount)?,
+            retained_fee: fee,
+            protocol_fee,
+            top_up: 0,
+        };
+
+        let balance = recipient_balance.saturating_add(payout.paid);
+        if balance > 0 && balance < recipient_minimum {
+            if self.top_up_recipients {
+                payout.top_up = (recipient_minimum - balance).min(payout.retained_fee);
+                payout.paid += payout.top_up;
+                payout.retained_fee -= payout.top_up;
+            }
+            require!(
+                recipient_balance.saturating_add(payout.paid) >= recipient_minimum,
+                ZkPoolError::RecipientBelowRentMinimum
+            );
+        }
+
+        Ok(payout)
+    }
+}
+
+/// `bps` basis points of `amount`, rounded down
+fn bps_of(amount: u64, bps: u16) -> u64 {
+    (amount as u128 * bps as u128 / crate::constants::BPS_DENOMINATOR as u128) as u64
+}
+
+/// Lamports moved by an unshield payout
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Payout {
+    /// Sent to the recipient, including any top-up
+    pub paid: u64,
+    /// Part of the fee kept by the treasury
+    pub retained_fee: u64,
+    /// Sent to the fee vault
+    pub protocol_fee: u64,
+    /// Part of the fee sent to the recipient to reach rent exemption
+    pub top_up: u64,
 }
 
 /// Verification key hashes for all circuits
@@ -45,10 +320,26 @@ pub struct VkHashes {
     pub unshield: [u8; 32],
 }
 
+/// Solvency figures returned by get_pool_stats
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct PoolStats {
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub total_fees: u64,
+    pub total_protocol_fees: u64,
+    pub expected_balance: u64,
+    pub treasury_balance: u64,
+    pub rent_exempt_minimum: u64,
+    pub tvl: u64,
+}
+
 /// Verification key storage for a single circuit
 #[account]
 pub struct VerificationKeyAccount {
-    /// Circuit identifier (0=shield, 1=transfer, 2=unshield)
+    /// Account layout version (ACCOUNT_VERSION)
+    pub version: u8,
+
+    /// Circuit identifier (0=shield, 1=transfer, 2=unshield, 3=batch_append)
     pub circuit: u8,
 
     /// Number of public inputs expected
@@ -65,15 +356,20 @@ pub struct VerificationKeyAccount {
 
     /// PDA bump
     pub bump: u8,
+
+    /// Zeroed space for future fields
+    pub reserved: [u8; VK_RESERVED_LEN],
 }
 
 impl VerificationKeyAccount {
     pub const BASE_LEN: usize = 8 + // discriminator
+        1 +  // version
         1 +  // circuit
         4 +  // n_public
         4 +  // vk_data vec length
         32 + // vk_hash
-        1; // bump
+        1 +  // bump
+        VK_RESERVED_LEN; // reserved
 
     pub fn space_for(n_public: u32) -> usize {
         Self::BASE_LEN + Self::vk_data_len(n_public)
@@ -85,16 +381,23 @@ impl VerificationKeyAccount {
         448 + (n_public as usize + 1) * 64
     }
 
-    /// Validate n_public matches expected count for circuit type
-    pub fn validate_n_public(&self) -> Result<()> {
+    /// Expected public input count for a circuit type
+    pub fn expected_public_inputs(circuit: u8) -> Result<usize> {
         use crate::constants::*;
 
-        let expected = match self.circuit {
-            CIRCUIT_SHIELD => SHIELD_PUBLIC_INPUTS,
-            CIRCUIT_TRANSFER => TRANSFER_PUBLIC_INPUTS,
-            CIRCUIT_UNSHIELD => UNSHIELD_PUBLIC_INPUTS,
-            _ => return Err(ZkPoolError::InvalidCircuitType.into()),
-        };
+        match circuit {
+            CIRCUIT_SHIELD => Ok(SHIELD_PUBLIC_INPUTS),
+            CIRCUIT_TRANSFER => Ok(TRANSFER_PUBLIC_INPUTS),
+            CIRCUIT_UNSHIELD => Ok(UNSHIELD_PUBLIC_INPUTS),
+            CIRCUIT_BATCH_APPEND => Ok(BATCH_APPEND_PUBLIC_INPUTS),
+            CIRCUIT_DISCLOSURE => Ok(DISCLOSURE_PUBLIC_INPUTS),
+            _ => Err(ZkPoolError::InvalidCircuitType.into()),
+        }
+    }
+
+    /// Validate n_public matches expected count for circuit type
+    pub fn validate_n_public(&self) -> Result<()> {
+        let expected = Self::expected_public_inputs(self.circuit)?;
 
         require!(
             self.n_public as usize == expected,
@@ -105,11 +408,37 @@ impl VerificationKeyAccount {
     }
 }
 
+/// Stored Merkle root with posting metadata
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct RootEntry {
+    /// Merkle root
+    pub root: [u8; 32],
+
+    /// Slot in which the root was posted
+    pub slot: u64,
+
+    /// Unix timestamp at which the root was posted
+    pub timestamp: i64,
+
+    /// Monotonically increasing sequence number (gap-free across posts)
+    pub seq: u64,
+
+    /// Set by revoke_root; revoked roots are never accepted
+    pub revoked: bool,
+}
+
+impl RootEntry {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 1;
+}
+
 /// Ring buffer of recent Merkle roots
 #[account]
 pub struct RootsAccount {
+    /// Account layout version (ACCOUNT_VERSION)
+    pub version: u8,
+
     /// Ring buffer of roots
-    pub roots: Vec<[u8; 32]>,
+    pub roots: Vec<RootEntry>,
 
     /// Current write cursor
     pub cursor: u16,
@@ -117,50 +446,204 @@ pub struct RootsAccount {
     /// Number of roots currently stored
     pub size: u16,
 
-    /// Maximum capacity (from PoolConfig.root_window)
+    /// Maximum capacity (PoolConfig.root_window when created, then set_root_window)
     pub capacity: u16,
 
+    /// Sequence number assigned to the next root
+    pub next_seq: u64,
+
     /// PDA bump
     pub bump: u8,
+
+    /// Commitment tree these roots belong to
+    pub tree_index: u32,
+
+    /// Leaves inserted into this tree so far
+    pub leaf_count: u64,
+
+    /// Zeroed space for future fields
+    pub reserved: [u8; ROOTS_RESERVED_LEN],
+}
+
+/// Extra PDA seed for a roots account
+///
+/// Tree 0 keeps the original `["roots"]` address (an empty seed does not
+/// change the derivation); later trees use `["roots", tree_index LE]`.
+pub fn tree_seed(tree_index: u32) -> Vec<u8> {
+    if tree_index == 0 {
+        Vec::new()
+    } else {
+        tree_index.to_le_bytes().to_vec()
+    }
 }
 
 impl RootsAccount {
     pub const BASE_LEN: usize = 8 + // discriminator
+        1 +  // version
         4 +  // roots vec length
         2 +  // cursor
         2 +  // size
         2 +  // capacity
-        1; // bump
+        8 +  // next_seq
+        1 +  // bump
+        4 +  // tree_index
+        8 +  // leaf_count
+        ROOTS_RESERVED_LEN; // reserved
 
     pub fn space_for(capacity: u16) -> usize {
-        Self::BASE_LEN + (capacity as usize * 32)
+        Self::BASE_LEN + (capacity as usize * RootEntry::LEN)
     }
 
-    /// Check if a root exists in the ring buffer
-    pub fn contains_root(&self, root: &[u8; 32]) -> bool {
+    /// Check if a root exists in the ring buffer, is not revoked and is recent enough
+    ///
+    /// `max_age_slots == 0` disables the age limit.
+    pub fn contains_root(&self, root: &[u8; 32], current_slot: u64, max_age_slots: u64) -> bool {
+        self.roots.iter().take(self.size as usize).any(|entry| {
+            entry.root == *root
+                && !entry.revoked
+                && (max_age_slots == 0 || current_slot.saturating_sub(entry.slot) <= max_age_slots)
+        })
+    }
+
+    /// Mark every live entry for `root` as revoked, returning the newest one
+    pub fn revoke_root(&mut self, root: &[u8; 32]) -> Result<RootEntry> {
+        let mut revoked: Option<RootEntry> = None;
+        for entry in self.roots.iter_mut().take(self.size as usize) {
+            if entry.root == *root && !entry.revoked {
+                entry.revoked = true;
+                match revoked {
+                    Some(newest) if newest.seq > entry.seq => {}
+                    _ => revoked = Some(*entry),
+                }
+            }
+        }
+
+        revoked.ok_or_else(|| ZkPoolError::RootNotFound.into())
+    }
+
+    /// Stored roots in insertion order (oldest first)
+    pub fn ordered_roots(&self) -> Vec<RootEntry> {
+        if self.capacity == 0 {
+            return Vec::new();
+        }
+
+        let capacity = self.capacity as usize;
+        let oldest = (self.cursor as usize + capacity - self.size as usize) % capacity;
+        (0..self.size as usize)
+            .map(|i| self.roots[(oldest + i) % capacity])
+            .collect()
+    }
+
+    /// Validate a new window for a tree currently holding `capacity` roots
+    ///
+    /// A single realloc may grow the account by at most
+    /// `MAX_PERMITTED_DATA_INCREASE` bytes, so large increases are made in steps.
+    pub fn checked_window(capacity: u16, new_capacity: u16) -> Result<u16> {
+        require!(
+            new_capacity > 0 && new_capacity <= MAX_ROOT_WINDOW,
+            ZkPoolError::InvalidRootWindow
+        );
+        let growth = Self::space_for(new_capacity).saturating_sub(Self::space_for(capacity));
+        require!(
+            growth <= MAX_PERMITTED_DATA_INCREASE,
+            ZkPoolError::RootWindowGrowthTooLarge
+        );
+
+        Ok(new_capacity)
+    }
+
+    /// Change the ring buffer capacity, keeping the most recent roots in order
+    ///
+    /// When shrinking below the current size, the oldest roots are dropped.
+    /// The account must already be reallocated to `space_for(new_capacity)`.
+    pub fn resize(&mut self, new_capacity: u16) {
+        let mut ordered = self.ordered_roots();
+        let keep = ordered.len().min(new_capacity as usize);
+        ordered.drain(..ordered.len() - keep);
+
+        let size = ordered.len() as u16;
+        ordered.resize(new_capacity as usize, RootEntry::default());
+
+        self.roots = ordered;
+        self.size = size;
+        self.cursor = size % new_capacity;
+        self.capacity = new_capacity;
+    }
+
+    /// Check if the tree has no free leaves left at `merkle_depth`
+    pub fn is_full(&self, merkle_depth: u8) -> bool {
+        self.leaf_count >= 1u64 << merkle_depth
+    }
+
+    /// Reserve the next leaf of the tree, returning its index
+    pub fn insert_leaf(&mut self, merkle_depth: u8) -> Result<u64> {
+        require!(!self.is_full(merkle_depth), ZkPoolError::TreeFull);
+
+        let leaf_index = self.leaf_count;
+        self.leaf_count += 1;
+        Ok(leaf_index)
+    }
+
+    /// Most recently added entry, if any
+    pub fn latest(&self) -> Option<&RootEntry> {
+        if self.size == 0 || self.capacity == 0 {
+            return None;
+        }
+
+        let capacity = self.capacity as usize;
         self.roots
-            .iter()
-            .take(self.size as usize)
-            .any(|r| r == root)
+            .get((self.cursor as usize + capacity - 1) % capacity)
     }
 
-    /// Add a new root to the ring buffer
-    pub fn add_root(&mut self, root: [u8; 32]) {
+    /// Add a new root to the ring buffer, returning its sequence number
+    ///
+    /// Rejects the zero (empty) root and any root already in the window,
+    /// including revoked entries, so a revoked root cannot be re-validated
+    /// by posting it again.
+    pub fn add_root(&mut self, root: [u8; 32], slot: u64, timestamp: i64) -> Result<u64> {
+        require!(self.capacity > 0, ZkPoolError::RootsNotInitialized);
+        require!(root != [0u8; 32], ZkPoolError::ZeroRoot);
+        require!(
+            self.latest().map(|entry| entry.root) != Some(root),
+            ZkPoolError::DuplicateRoot
+        );
+        require!(
+            !self
+                .roots
+                .iter()
+                .take(self.size as usize)
+                .any(|entry| entry.root == root),
+            ZkPoolError::RootAlreadyInWindow
+        );
+
+        let seq = self.next_seq;
         let idx = self.cursor as usize;
         if idx < self.roots.len() {
-            self.roots[idx] = root;
+            self.roots[idx] = RootEntry {
+                root,
+                slot,
+                timestamp,
+                seq,
+                revoked: false,
+            };
         }
 
+        self.next_seq += 1;
         self.cursor = (self.cursor + 1) % self.capacity;
         if self.size < self.capacity {
             self.size += 1;
         }
+
+        Ok(seq)
     }
 }
 
 /// Nullifier storage (sharded for scalability)
 #[account]
 pub struct NullifiersAccount {
+    /// Account layout version (ACCOUNT_VERSION)
+    pub version: u8,
+
     /// Shard identifier
     pub shard: u16,
 
@@ -169,13 +652,18 @@ pub struct NullifiersAccount {
 
     /// PDA bump
     pub bump: u8,
+
+    /// Zeroed space for future fields
+    pub reserved: [u8; NULLIFIERS_RESERVED_LEN],
 }
 
 impl NullifiersAccount {
     pub const BASE_LEN: usize = 8 + // discriminator
+        1 +  // version
         2 +  // shard
         4 +  // nullifiers vec length
-        1; // bump
+        1 +  // bump
+        NULLIFIERS_RESERVED_LEN; // reserved
 
     pub fn space_for(capacity: usize) -> usize {
         Self::BASE_LEN + (capacity * 32)
@@ -215,3 +703,1673 @@ pub fn get_nullifier_shard(_nullifier: &[u8; 32]) -> u16 {
     // For MVP, always return shard 0
     0
 }
+
+/// Built-in M-of-N signer set that acts as the pool admin
+///
+/// Once created, `PoolConfig.admin` points at this PDA. Admin instructions are
+/// then reached only through approved proposals, which `execute_proposal`
+/// replays as a CPI into this program signed by the multisig PDA.
+#[account]
+pub struct Multisig {
+    /// Account layout version (ACCOUNT_VERSION)
+    pub version: u8,
+
+    /// Members allowed to propose and approve
+    pub signers: Vec<Pubkey>,
+
+    /// Approvals required to execute a proposal
+    pub threshold: u8,
+
+    /// Number of proposals created (next proposal index)
+    pub proposal_count: u64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Zeroed space for future fields
+    pub reserved: [u8; MULTISIG_RESERVED_LEN],
+}
+
+impl Multisig {
+    pub const LEN: usize = 8 + // discriminator
+        1 +  // version
+        4 + (32 * crate::constants::MAX_MULTISIG_SIGNERS) + // signers
+        1 +  // threshold
+        8 +  // proposal_count
+        1 +  // bump
+        MULTISIG_RESERVED_LEN; // reserved
+
+    /// Validate a signer set and threshold before storing them
+    pub fn validate_signers(signers: &[Pubkey], threshold: u8) -> Result<()> {
+        use crate::constants::MAX_MULTISIG_SIGNERS;
+
+        require!(
+            !signers.is_empty() && signers.len() <= MAX_MULTISIG_SIGNERS,
+            ZkPoolError::InvalidMultisigSigners
+        );
+
+        for (i, signer) in signers.iter().enumerate() {
+            require!(
+                !signers[i + 1..].contains(signer),
+                ZkPoolError::InvalidMultisigSigners
+            );
+        }
+
+        require!(
+            threshold > 0 && threshold as usize <= signers.len(),
+            ZkPoolError::InvalidMultisigThreshold
+        );
+
+        Ok(())
+    }
+
+    /// Check if a key is a member of the signer set
+    pub fn is_signer(&self, key: &Pubkey) -> bool {
+        self.signers.contains(key)
+    }
+}
+
+/// Account meta recorded in a proposal (mirrors `AccountMeta`)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct ProposalAccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl ProposalAccountMeta {
+    pub const LEN: usize = 32 + 1 + 1;
+}
+
+/// Admin instruction awaiting multisig approval
+#[account]
+pub struct Proposal {
+    /// Account layout version (ACCOUNT_VERSION)
+    pub version: u8,
+
+    /// Multisig this proposal belongs to
+    pub multisig: Pubkey,
+
+    /// Sequential proposal index (PDA seed)
+    pub index: u64,
+
+    /// Signer that created the proposal
+    pub proposer: Pubkey,
+
+    /// Accounts of the zk-pool instruction to execute
+    pub accounts: Vec<ProposalAccountMeta>,
+
+    /// Instruction data (Anchor discriminator + serialized arguments)
+    pub data: Vec<u8>,
+
+    /// Signers that approved this proposal
+    pub approvals: Vec<Pubkey>,
+
+    /// Set once the instruction has been executed
+    pub executed: bool,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Zeroed space for future fields
+    pub reserved: [u8; PROPOSAL_RESERVED_LEN],
+}
+
+impl Proposal {
+    pub const BASE_LEN: usize = 8 + // discriminator
+        1 +  // version
+        32 + // multisig
+        8 +  // index
+        32 + // proposer
+        4 +  // accounts vec length
+        4 +  // data vec length
+        4 + (32 * crate::constants::MAX_MULTISIG_SIGNERS) + // approvals
+        1 +  // executed
+        1 +  // bump
+        PROPOSAL_RESERVED_LEN; // reserved
+
+    pub fn space_for(n_accounts: usize, data_len: usize) -> usize {
+        Self::BASE_LEN + (n_accounts * ProposalAccountMeta::LEN) + data_len
+    }
+
+    /// Count approvals from signers still in the multisig
+    pub fn approval_count(&self, multisig: &Multisig) -> usize {
+        self.approvals
+            .iter()
+            .filter(|a| multisig.is_signer(a))
+            .count()
+    }
+
+    /// Record an approval from a multisig member, once per member
+    pub fn approve(&mut self, multisig: &Multisig, signer: Pubkey) -> Result<()> {
+        require!(multisig.is_signer(&signer), ZkPoolError::NotMultisigSigner);
+        require!(!self.executed, ZkPoolError::ProposalAlreadyExecuted);
+        require!(
+            !self.approvals.contains(&signer),
+            ZkPoolError::ProposalAlreadyApproved
+        );
+
+        // Approvals from removed signers are dropped so the list stays within
+        // the space reserved for MAX_MULTISIG_SIGNERS entries
+        self.approvals.retain(|a| multisig.is_signer(a));
+        self.approvals.push(signer);
+        Ok(())
+    }
+
+    /// Mark the proposal executed once it has reached the threshold
+    ///
+    /// Called before the CPI so the proposal cannot be replayed.
+    pub fn mark_executed(&mut self, multisig: &Multisig) -> Result<()> {
+        require!(!self.executed, ZkPoolError::ProposalAlreadyExecuted);
+        require!(
+            self.approval_count(multisig) >= multisig.threshold as usize,
+            ZkPoolError::ProposalThresholdNotMet
+        );
+
+        self.executed = true;
+        Ok(())
+    }
+
+    /// Account metas of the proposed instruction, checked against the keys
+    /// passed to execute (flags always come from the proposal)
+    pub fn instruction_metas(&self, passed: &[Pubkey]) -> Result<Vec<AccountMeta>> {
+        require!(
+            passed.len() == self.accounts.len(),
+            ZkPoolError::ProposalAccountMismatch
+        );
+
+        self.accounts
+            .iter()
+            .zip(passed)
+            .map(|(meta, key)| {
+                require!(meta.pubkey == *key, ZkPoolError::ProposalAccountMismatch);
+                Ok(AccountMeta {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Registered root attesters for attestation mode
+#[account]
+pub struct AttesterSet {
+    /// Account layout version (ACCOUNT_VERSION)
+    pub version: u8,
+
+    /// Keys allowed to call attest_root
+    pub attesters: Vec<Pubkey>,
+
+    /// Distinct attestations required before a root is accepted
+    pub threshold: u8,
+
+    /// Slots after which a pending attestation round expires
+    pub expiry_slots: u64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Zeroed space for future fields
+    pub reserved: [u8; ATTESTERS_RESERVED_LEN],
+}
+
+impl AttesterSet {
+    pub const LEN: usize = 8 + // discriminator
+        1 +  // version
+        4 + (32 * crate::constants::MAX_ATTESTERS) + // attesters
+        1 +  // threshold
+        8 +  // expiry_slots
+        1 +  // bump
+        ATTESTERS_RESERVED_LEN; // reserved
+
+    /// Validate an attester set before storing it
+    pub fn validate(attesters: &[Pubkey], threshold: u8, expiry_slots: u64) -> Result<()> {
+        use crate::constants::MAX_ATTESTERS;
+
+        require!(
+            !attesters.is_empty() && attesters.len() <= MAX_ATTESTERS,
+            ZkPoolError::InvalidAttesters
+        );
+
+        for (i, attester) in attesters.iter().enumerate() {
+            require!(
+                !attesters[i + 1..].contains(attester),
+                ZkPoolError::InvalidAttesters
+            );
+        }
+
+        require!(
+            threshold > 0 && threshold as usize <= attesters.len() && expiry_slots > 0,
+            ZkPoolError::InvalidAttestationThreshold
+        );
+
+        Ok(())
+    }
+
+    /// Check if a key is a registered attester
+    pub fn is_attester(&self, key: &Pubkey) -> bool {
+        self.attesters.contains(key)
+    }
+}
+
+/// Commitments of one tree waiting to be appended by batch_append
+#[account]
+pub struct CommitmentQueue {
+    /// Account layout version (ACCOUNT_VERSION)
+    pub version: u8,
+
+    /// Tree the queued leaves belong to (PDA seed)
+    pub tree_index: u32,
+
+    /// Tree root after the last appended batch ([0; 32] = empty tree)
+    pub current_root: [u8; 32],
+
+    /// Leaf index of the first queued commitment
+    pub next_index: u64,
+
+    /// Queued commitments, oldest first
+    pub leaves: Vec<[u8; 32]>,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Zeroed space for future fields
+    pub reserved: [u8; QUEUE_RESERVED_LEN],
+}
+
+impl CommitmentQueue {
+    pub const LEN: usize = 8 + // discriminator
+        1 +  // version
+        4 +  // tree_index
+        32 + // current_root
+        8 +  // next_index
+        4 + (32 * crate::constants::COMMITMENT_QUEUE_CAPACITY) + // leaves
+        1 +  // bump
+        QUEUE_RESERVED_LEN; // reserved
+
+    /// Queue a commitment for the next batch
+    pub fn push(&mut self, commitment: [u8; 32]) -> Result<()> {
+        require!(
+            self.leaves.len() < crate::constants::COMMITMENT_QUEUE_CAPACITY,
+            ZkPoolError::QueueFull
+        );
+        self.leaves.push(commitment);
+        Ok(())
+    }
+
+    /// Leaves of the next batch, zero-padded when `allow_partial` is set,
+    /// together with the number of real leaves
+    pub fn next_batch(
+        &self,
+        allow_partial: bool,
+    ) -> Result<([[u8; 32]; crate::constants::BATCH_APPEND_SIZE], usize)> {
+        use crate::constants::BATCH_APPEND_SIZE;
+
+        let count = self.leaves.len().min(BATCH_APPEND_SIZE);
+        require!(
+            count == BATCH_APPEND_SIZE || (allow_partial && count > 0),
+            ZkPoolError::BatchNotReady
+        );
+
+        let mut batch = [[0u8; 32]; BATCH_APPEND_SIZE];
+        batch[..count].copy_from_slice(&self.leaves[..count]);
+        Ok((batch, count))
+    }
+
+    /// Remove an appended batch and record the resulting root
+    ///
+    /// Padding leaves still occupy tree positions, so the index always
+    /// advances by a full batch.
+    pub fn pop_batch(&mut self, count: usize, new_root: [u8; 32]) {
+        self.leaves.drain(..count);
+        self.next_index += crate::constants::BATCH_APPEND_SIZE as u64;
+        self.current_root = new_root;
+    }
+}
+
+/// Ring buffer of approved association set roots (Privacy Pools style)
+#[account]
+pub struct AssociationRoots {
+    /// Account layout version (ACCOUNT_VERSION)
+    pub version: u8,
+
+    /// Key allowed to add roots besides the admin (default = admin only)
+    pub curator: Pubkey,
+
+    /// Ring buffer of roots, at most ASSOCIATION_ROOT_WINDOW
+    pub roots: Vec<[u8; 32]>,
+
+    /// Next write position
+    pub cursor: u16,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Zeroed space for future fields
+    pub reserved: [u8; ASSOCIATION_RESERVED_LEN],
+}
+
+impl AssociationRoots {
+    pub const LEN: usize = 8 + // discriminator
+        1 +  // version
+        32 + // curator
+        4 + (32 * crate::constants::ASSOCIATION_ROOT_WINDOW) + // roots
+        2 +  // cursor
+        1 +  // bump
+        ASSOCIATION_RESERVED_LEN; // reserved
+
+    /// Check if `authority` may add association roots
+    pub fn is_manager(&self, authority: &Pubkey, admin: &Pubkey) -> bool {
+        authority == admin || (self.curator != Pubkey::default() && *authority == self.curator)
+    }
+
+    /// Check if a non-zero root is in the window
+    pub fn contains_root(&self, root: &[u8; 32]) -> bool {
+        *root != [0u8; 32] && self.roots.contains(root)
+    }
+
+    /// Add a root, overwriting the oldest once the window is full
+    ///
+    /// Returns the index the root was written to.
+    pub fn add_root(&mut self, root: [u8; 32]) -> Result<u16> {
+        use crate::constants::ASSOCIATION_ROOT_WINDOW;
+
+        require!(root != [0u8; 32], ZkPoolError::ZeroRoot);
+        require!(
+            !self.roots.contains(&root),
+            ZkPoolError::RootAlreadyInWindow
+        );
+
+        let index = self.cursor;
+        if (index as usize) < self.roots.len() {
+            self.roots[index as usize] = root;
+        } else {
+            self.roots.push(root);
+        }
+        self.cursor = ((index as usize + 1) % ASSOCIATION_ROOT_WINDOW) as u16;
+        Ok(index)
+    }
+}
+
+/// Recipients unshield must not pay out to, kept sorted for binary search
+#[account]
+pub struct DenyList {
+    /// Account layout version (ACCOUNT_VERSION)
+    pub version: u8,
+
+    /// Compliance key allowed to edit the list besides the admin (default = admin only)
+    pub compliance: Pubkey,
+
+    /// Denied recipients in ascending order, at most MAX_DENY_LIST_ENTRIES
+    pub entries: Vec<Pubkey>,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Zeroed space for future fields
+    pub reserved: [u8; DENY_LIST_RESERVED_LEN],
+}
+
+impl DenyList {
+    pub const LEN: usize = 8 + // discriminator
+        1 +  // version
+        32 + // compliance
+        4 + (32 * crate::constants::MAX_DENY_LIST_ENTRIES) + // entries
+        1 +  // bump
+        DENY_LIST_RESERVED_LEN; // reserved
+
+    /// Check if `authority` may edit the list
+    pub fn is_manager(&self, authority: &Pubkey, admin: &Pubkey) -> bool {
+        authority == admin
+            || (self.compliance != Pubkey::default() && *authority == self.compliance)
+    }
+
+    /// Check if a recipient is denied
+    pub fn is_denied(&self, recipient: &Pubkey) -> bool {
+        self.entries.binary_search(recipient).is_ok()
+    }
+
+    /// Add or remove a recipient; returns false if the list was already in that state
+    pub fn set_denied(&mut self, recipient: Pubkey, denied: bool) -> Result<bool> {
+        match (self.entries.binary_search(&recipient), denied) {
+            (Err(pos), true) => {
+                require!(
+                    self.entries.len() < crate::constants::MAX_DENY_LIST_ENTRIES,
+                    ZkPoolError::DenyListFull
+                );
+                self.entries.insert(pos, recipient);
+                Ok(true)
+            }
+            (Ok(pos), false) => {
+                self.entries.remove(pos);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+/// Depositor credential for permissioned pools, one per wallet
+#[account]
+pub struct Credential {
+    /// Account layout version (ACCOUNT_VERSION)
+    pub version: u8,
+
+    /// Wallet the credential was issued to (PDA seed)
+    pub holder: Pubkey,
+
+    /// Issuer that signed and paid for the credential (refunded on revoke)
+    pub issuer: Pubkey,
+
+    /// Unix timestamp after which the credential is no longer valid
+    pub expires_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Zeroed space for future fields
+    pub reserved: [u8; CREDENTIAL_RESERVED_LEN],
+}
+
+impl Credential {
+    pub const LEN: usize = 8 + // discriminator
+        1 +  // version
+        32 + // holder
+        32 + // issuer
+        8 +  // expires_at
+        1 +  // bump
+        CREDENTIAL_RESERVED_LEN; // reserved
+
+    /// Valid if issued by the current issuer and not expired at `now`
+    ///
+    /// Rotating the issuer invalidates every credential of the previous one.
+    pub fn is_valid(&self, issuer: &Pubkey, now: i64) -> bool {
+        self.issuer == *issuer && now < self.expires_at
+    }
+}
+
+/// Unshield held back by the withdrawal delay, one per nullifier
+#[account]
+pub struct PendingWithdrawal {
+    /// Account layout version (ACCOUNT_VERSION)
+    pub version: u8,
+
+    /// Nullifier spent by the proof (PDA seed)
+    pub nullifier: [u8; 32],
+
+    /// Wallet paid on claim
+    pub recipient: Pubkey,
+
+    /// Unshielded amount, including the fee
+    pub amount: u64,
+
+    /// Fee kept by the pool
+    pub fee: u64,
+
+    /// Association root the proof used (zero if opted out)
+    pub association_root: [u8; 32],
+
+    /// First slot at which the withdrawal can be claimed
+    pub claimable_slot: u64,
+
+    /// Account that paid the rent (refunded on claim)
+    pub payer: Pubkey,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Zeroed space for future fields
+    pub reserved: [u8; PENDING_WITHDRAWAL_RESERVED_LEN],
+}
+
+impl PendingWithdrawal {
+    pub const LEN: usize = 8 + // discriminator
+        1 +  // version
+        32 + // nullifier
+        32 + // recipient
+        8 +  // amount
+        8 +  // fee
+        32 + // association_root
+        8 +  // claimable_slot
+        32 + // payer
+        1 +  // bump
+        PENDING_WITHDRAWAL_RESERVED_LEN; // reserved
+
+    /// PDA seed taken from the nullifier public input
+    ///
+    /// Malformed inputs map to a zero seed and are rejected by the handler.
+    pub fn nullifier_seed(public_inputs: &[[u8; 32]]) -> [u8; 32] {
+        public_inputs.get(1).copied().unwrap_or_default()
+    }
+
+    /// Check if the delay has elapsed at `current_slot`
+    pub fn is_claimable(&self, current_slot: u64) -> bool {
+        current_slot >= self.claimable_slot
+    }
+}
+
+/// Running totals of value entering and leaving the treasury
+///
+/// `expected_balance` is what the treasury must hold on top of its rent-exempt
+/// minimum: deposits minus payouts, with retained fees staying in.
+#[account]
+pub struct PoolState {
+    /// Account layout version (ACCOUNT_VERSION)
+    pub version: u8,
+
+    /// Sum of all shielded amounts
+    pub total_deposited: u64,
+
+    /// Sum of all amounts paid to unshield recipients
+    pub total_withdrawn: u64,
+
+    /// Sum of all fees retained by the treasury
+    pub total_fees: u64,
+
+    /// Lamports the treasury must hold above its rent-exempt minimum
+    pub expected_balance: u64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Sum of all unshield protocol fees moved to the fee vault
+    pub total_protocol_fees: u64,
+
+    /// Zeroed space for future fields
+    pub reserved: [u8; POOL_STATE_RESERVED_LEN],
+}
+
+impl PoolState {
+    pub const LEN: usize = 8 + // discriminator
+        1 +  // version
+        8 +  // total_deposited
+        8 +  // total_withdrawn
+        8 +  // total_fees
+        8 +  // expected_balance
+        1 +  // bump
+        8 +  // total_protocol_fees
+        POOL_STATE_RESERVED_LEN; // reserved
+
+    /// Record a deposit into the treasury
+    pub fn record_deposit(&mut self, amount: u64) -> Result<()> {
+        self.total_deposited = self
+            .total_deposited
+            .checked_add(amount)
+            .ok_or(ZkPoolError::ArithmeticOverflow)?;
+        self.expected_balance = self
+            .expected_balance
+            .checked_add(amount)
+            .ok_or(ZkPoolError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Record a payout of `paid` lamports and `protocol_fee` lamports to the
+    /// fee vault; the `fee` stays in the treasury
+    pub fn record_withdrawal(&mut self, paid: u64, fee: u64, protocol_fee: u64) -> Result<()> {
+        self.expected_balance = paid
+            .checked_add(protocol_fee)
+            .and_then(|outflow| self.expected_balance.checked_sub(outflow))
+            .ok_or(ZkPoolError::TreasuryInsolvent)?;
+        self.total_protocol_fees = self
+            .total_protocol_fees
+            .checked_add(protocol_fee)
+            .ok_or(ZkPoolError::ArithmeticOverflow)?;
+        self.total_withdrawn = self
+            .total_withdrawn
+            .checked_add(paid)
+            .ok_or(ZkPoolError::ArithmeticOverflow)?;
+        self.total_fees = self
+            .total_fees
+            .checked_add(fee)
+            .ok_or(ZkPoolError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Check the treasury covers the expected balance plus its rent-exempt minimum
+    pub fn check_solvency(&self, treasury_balance: u64, rent_exempt_minimum: u64) -> Result<()> {
+        let required = self
+            .expected_balance
+            .checked_add(rent_exempt_minimum)
+            .ok_or(ZkPoolError::ArithmeticOverflow)?;
+        require!(treasury_balance >= required, ZkPoolError::TreasuryInsolvent);
+        Ok(())
+    }
+}
+
+/// Protocol fee vault; the fees are the lamports held above its rent
+#[account]
+pub struct FeeVault {
+    /// Account layout version (ACCOUNT_VERSION)
+    pub version: u8,
+
+    /// Key allowed to withdraw collected fees (default = nobody)
+    pub fee_manager: Pubkey,
+
+    /// Sum of all protocol fees received
+    pub total_collected: u64,
+
+    /// Sum of all protocol fees withdrawn
+    pub total_withdrawn: u64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Zeroed space for future fields
+    pub reserved: [u8; FEE_VAULT_RESERVED_LEN],
+}
+
+impl FeeVault {
+    pub const LEN: usize = 8 + // discriminator
+        1 +  // version
+        32 + // fee_manager
+        8 +  // total_collected
+        8 +  // total_withdrawn
+        1 +  // bump
+        FEE_VAULT_RESERVED_LEN; // reserved
+
+    /// Record protocol fees received by the vault
+    pub fn record_collected(&mut self, amount: u64) -> Result<()> {
+        self.total_collected = self
+            .total_collected
+            .checked_add(amount)
+            .ok_or(ZkPoolError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+/// Leaky-bucket limit on treasury outflow
+///
+/// `outflow` rises with every payout and drains linearly at
+/// `max_outflow / window_slots` per slot, so at most `max_outflow` leaves the
+/// pool in any `window_slots` window.
+#[account]
+pub struct TreasuryState {
+    /// Account layout version (ACCOUNT_VERSION)
+    pub version: u8,
+
+    /// Bucket capacity in lamports (0 disables the limit)
+    pub max_outflow: u64,
+
+    /// Slots for a full bucket to drain
+    pub window_slots: u64,
+
+    /// Current bucket level
+    pub outflow: u64,
+
+    /// Slot the level was last updated
+    pub last_slot: u64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Zeroed space for future fields
+    pub reserved: [u8; TREASURY_STATE_RESERVED_LEN],
+}
+
+impl TreasuryState {
+    pub const LEN: usize = 8 + // discriminator
+        1 +  // version
+        8 +  // max_outflow
+        8 +  // window_slots
+        8 +  // outflow
+        8 +  // last_slot
+        1 +  // bump
+        TREASURY_STATE_RESERVED_LEN; // reserved
+
+    /// Drain the bucket for the slots elapsed since `last_slot`
+    fn leak(&mut self, current_slot: u64) {
+        let elapsed = current_slot.saturating_sub(self.last_slot);
+        if self.window_slots > 0 {
+            let drained = (self.max_outflow as u128 * elapsed as u128) / self.window_slots as u128;
+            self.outflow = self
+                .outflow
+                .saturating_sub(drained.min(u64::MAX as u128) as u64);
+        }
+        self.last_slot = self.last_slot.max(current_slot);
+    }
+
+    /// Apply new limits, settling the level accrued under the old ones
+    pub fn configure(
+        &mut self,
+        max_outflow: u64,
+        window_slots: u64,
+        current_slot: u64,
+    ) -> Result<()> {
+        require!(
+            max_outflow == 0 || window_slots > 0,
+            ZkPoolError::InvalidRateLimit
+        );
+
+        self.leak(current_slot);
+        self.max_outflow = max_outflow;
+        self.window_slots = window_slots;
+        if max_outflow == 0 {
+            self.outflow = 0;
+        }
+        Ok(())
+    }
+
+    /// Record a payout of `amount`, failing if the bucket would overflow
+    pub fn record_outflow(&mut self, amount: u64, current_slot: u64) -> Result<()> {
+        if self.max_outflow == 0 {
+            return Ok(());
+        }
+
+        self.leak(current_slot);
+        let outflow = self
+            .outflow
+            .checked_add(amount)
+            .ok_or(ZkPoolError::ArithmeticOverflow)?;
+        require!(
+            outflow <= self.max_outflow,
+            ZkPoolError::WithdrawalRateLimited
+        );
+
+        self.outflow = outflow;
+        Ok(())
+    }
+}
+
+/// Attestations collected for a root that has not reached the threshold yet
+#[account]
+pub struct PendingRoot {
+    /// Account layout version (ACCOUNT_VERSION)
+    pub version: u8,
+
+    /// Root being attested (PDA seed)
+    pub root: [u8; 32],
+
+    /// Attesters that signed in the current round
+    pub attesters: Vec<Pubkey>,
+
+    /// Slot of the first attestation in the current round
+    pub first_slot: u64,
+
+    /// Account that paid the rent (refunded on close)
+    pub payer: Pubkey,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Tree the root is attested for
+    pub tree_index: u32,
+
+    /// Zeroed space for future fields
+    pub reserved: [u8; PENDING_ROOT_RESERVED_LEN],
+}
+
+impl PendingRoot {
+    pub const LEN: usize = 8 + // discriminator
+        1 +  // version
+        32 + // root
+        4 + (32 * crate::constants::MAX_ATTESTERS) + // attesters
+        8 +  // first_slot
+        32 + // payer
+        1 +  // bump
+        4 +  // tree_index
+        PENDING_ROOT_RESERVED_LEN; // reserved
+
+    /// Check if the current round has expired at `current_slot`
+    pub fn is_expired(&self, current_slot: u64, expiry_slots: u64) -> bool {
+        current_slot.saturating_sub(self.first_slot) > expiry_slots
+    }
+
+    /// Count attestations from keys still registered as attesters
+    pub fn attestation_count(&self, set: &AttesterSet) -> usize {
+        self.attesters.iter().filter(|a| set.is_attester(a)).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::*;
+
+    /// Config with every optional feature off; tests set only what they exercise
+    fn test_config() -> PoolConfig {
+        PoolConfig {
+            version: ACCOUNT_VERSION,
+            admin: Pubkey::default(),
+            merkle_depth: DEFAULT_MERKLE_DEPTH,
+            root_window: DEFAULT_ROOT_WINDOW,
+            abi_hash: [0u8; 32],
+            vk_hashes: VkHashes::default(),
+            paused_mask: 0,
+            paused_at: 0,
+            max_pause_duration: 0,
+            bump: 255,
+            max_root_age_slots: 0,
+            root_attestation: false,
+            root_signer: Pubkey::default(),
+            active_tree: 0,
+            batch_append_vk_hash: [0u8; 32],
+            disclosure_vk_hash: [0u8; 32],
+            require_credential: false,
+            credential_issuer: Pubkey::default(),
+            max_shield_amount: 0,
+            max_unshield_amount: 0,
+            tvl_cap: 0,
+            tvl: 0,
+            withdrawal_delay_threshold: 0,
+            withdrawal_delay_slots: 0,
+            guardian: Pubkey::default(),
+            top_up_recipients: false,
+            shield_fee_bps: 0,
+            unshield_fee_bps: 0,
+            denomination: 0,
+            reserved: [0u8; CONFIG_RESERVED_LEN],
+        }
+    }
+
+    #[test]
+    fn test_unshield_pause_escape_hatch() {
+        let mut config = test_config();
+        config.paused_mask = PAUSE_UNSHIELD;
+        config.paused_at = 1_000;
+        config.max_pause_duration = 100;
+
+        assert!(config.is_unshield_paused(1_000));
+        assert!(config.is_unshield_paused(1_099));
+        assert!(!config.is_unshield_paused(1_100));
+
+        // Escape hatch disabled
+        config.max_pause_duration = 0;
+        assert!(config.is_unshield_paused(i64::MAX));
+
+        // Other flows paused, unshield open
+        let mut config = test_config();
+        config.paused_mask = PAUSE_SHIELD | PAUSE_TRANSFER;
+        config.max_pause_duration = 100;
+        assert!(config.is_paused(CIRCUIT_SHIELD));
+        assert!(!config.is_unshield_paused(0));
+    }
+
+    #[test]
+    fn test_pause_mask_is_per_circuit() {
+        let mut config = test_config();
+        config.max_pause_duration = 100;
+
+        // Pausing shield leaves transfer and unshield open
+        assert_eq!(
+            config.set_paused(PAUSE_SHIELD, true, 1_000).unwrap(),
+            PAUSE_SHIELD
+        );
+        assert!(config.is_paused(CIRCUIT_SHIELD));
+        assert!(!config.is_paused(CIRCUIT_TRANSFER));
+        assert!(!config.is_unshield_paused(1_000));
+        assert_eq!(config.paused_at, 0);
+
+        // Pausing unshield starts the escape-hatch clock; re-pausing does not move it
+        assert_eq!(
+            config.set_paused(PAUSE_UNSHIELD, true, 2_000).unwrap(),
+            PAUSE_UNSHIELD
+        );
+        assert_eq!(
+            config.set_paused(PAUSE_ALL, true, 2_050).unwrap(),
+            PAUSE_TRANSFER
+        );
+        assert_eq!(config.paused_at, 2_000);
+        assert!(config.is_unshield_paused(2_099));
+
+        // Unpausing shield only clears its bit
+        assert_eq!(
+            config.set_paused(PAUSE_SHIELD, false, 3_000).unwrap(),
+            PAUSE_SHIELD
+        );
+        assert_eq!(config.paused_mask, PAUSE_TRANSFER | PAUSE_UNSHIELD);
+        assert_eq!(config.paused_at, 2_000);
+
+        // Unpausing unshield resets the clock
+        assert_eq!(
+            config.set_paused(PAUSE_UNSHIELD, false, 3_000).unwrap(),
+            PAUSE_UNSHIELD
+        );
+        assert_eq!(config.paused_at, 0);
+
+        // Empty and unknown bits are rejected
+        for mask in [0, 1 << 3, PAUSE_ALL | 1 << 7] {
+            assert_eq!(
+                config.set_paused(mask, true, 0).unwrap_err(),
+                ZkPoolError::InvalidCircuitMask.into()
+            );
+        }
+    }
+
+    fn multisig_of(signers: &[Pubkey], threshold: u8) -> Multisig {
+        Multisig {
+            version: ACCOUNT_VERSION,
+            signers: signers.to_vec(),
+            threshold,
+            proposal_count: 1,
+            bump: 255,
+            reserved: [0u8; MULTISIG_RESERVED_LEN],
+        }
+    }
+
+    fn proposal_for(proposer: Pubkey, accounts: Vec<ProposalAccountMeta>) -> Proposal {
+        Proposal {
+            version: ACCOUNT_VERSION,
+            multisig: Pubkey::new_unique(),
+            index: 0,
+            proposer,
+            accounts,
+            data: vec![0u8; 16],
+            approvals: vec![proposer],
+            executed: false,
+            bump: 255,
+            reserved: [0u8; PROPOSAL_RESERVED_LEN],
+        }
+    }
+
+    #[test]
+    fn test_multisig_signer_set() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert!(Multisig::validate_signers(&[a, b], 2).is_ok());
+        assert_eq!(
+            Multisig::validate_signers(&[], 1).unwrap_err(),
+            ZkPoolError::InvalidMultisigSigners.into()
+        );
+        assert_eq!(
+            Multisig::validate_signers(&[a, b, a], 2).unwrap_err(),
+            ZkPoolError::InvalidMultisigSigners.into()
+        );
+        let too_many: Vec<Pubkey> = (0..=MAX_MULTISIG_SIGNERS)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        assert_eq!(
+            Multisig::validate_signers(&too_many, 1).unwrap_err(),
+            ZkPoolError::InvalidMultisigSigners.into()
+        );
+        for threshold in [0, 3] {
+            assert_eq!(
+                Multisig::validate_signers(&[a, b], threshold).unwrap_err(),
+                ZkPoolError::InvalidMultisigThreshold.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_proposal_threshold_and_approvals() {
+        let (a, b, c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let multisig = multisig_of(&[a, b, c], 2);
+        let mut proposal = proposal_for(a, Vec::new());
+
+        // The proposer's implicit approval is below the threshold
+        assert_eq!(
+            proposal.mark_executed(&multisig).unwrap_err(),
+            ZkPoolError::ProposalThresholdNotMet.into()
+        );
+        assert!(!proposal.executed);
+
+        // Approving twice does not count twice
+        assert_eq!(
+            proposal.approve(&multisig, a).unwrap_err(),
+            ZkPoolError::ProposalAlreadyApproved.into()
+        );
+
+        // Non-members cannot approve
+        let outsider = Pubkey::new_unique();
+        assert_eq!(
+            proposal.approve(&multisig, outsider).unwrap_err(),
+            ZkPoolError::NotMultisigSigner.into()
+        );
+        assert_eq!(proposal.approval_count(&multisig), 1);
+
+        proposal.approve(&multisig, b).unwrap();
+        assert_eq!(proposal.approval_count(&multisig), 2);
+        proposal.mark_executed(&multisig).unwrap();
+        assert!(proposal.executed);
+
+        // An executed proposal can be neither re-executed nor approved
+        assert_eq!(
+            proposal.mark_executed(&multisig).unwrap_err(),
+            ZkPoolError::ProposalAlreadyExecuted.into()
+        );
+        assert_eq!(
+            proposal.approve(&multisig, c).unwrap_err(),
+            ZkPoolError::ProposalAlreadyExecuted.into()
+        );
+    }
+
+    #[test]
+    fn test_proposal_ignores_removed_signers() {
+        let (a, b, c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let mut proposal = proposal_for(a, Vec::new());
+        proposal.approve(&multisig_of(&[a, b, c], 2), b).unwrap();
+
+        // After rotating b out, its approval no longer counts
+        let rotated = multisig_of(&[a, c], 2);
+        assert_eq!(
+            proposal.mark_executed(&rotated).unwrap_err(),
+            ZkPoolError::ProposalThresholdNotMet.into()
+        );
+        proposal.approve(&rotated, c).unwrap();
+        assert_eq!(proposal.approvals, vec![a, c]);
+        proposal.mark_executed(&rotated).unwrap();
+    }
+
+    #[test]
+    fn test_proposal_rejects_forged_accounts() {
+        let multisig_pda = Pubkey::new_unique();
+        let config = Pubkey::new_unique();
+        let proposal = proposal_for(
+            Pubkey::new_unique(),
+            vec![
+                ProposalAccountMeta {
+                    pubkey: config,
+                    is_signer: false,
+                    is_writable: true,
+                },
+                ProposalAccountMeta {
+                    pubkey: multisig_pda,
+                    is_signer: true,
+                    is_writable: false,
+                },
+            ],
+        );
+
+        // The approved accounts pass with the approved flags
+        let metas = proposal.instruction_metas(&[config, multisig_pda]).unwrap();
+        assert!(metas[1].is_signer && !metas[1].is_writable);
+        assert_eq!(metas[1].pubkey, multisig_pda);
+
+        // Swapping in another admin, reordering or dropping accounts is rejected,
+        // so the multisig signature cannot be lent to an unapproved account list
+        let forged_admin = Pubkey::new_unique();
+        for passed in [
+            vec![config, forged_admin],
+            vec![multisig_pda, config],
+            vec![config],
+            vec![config, multisig_pda, forged_admin],
+        ] {
+            assert_eq!(
+                proposal.instruction_metas(&passed).unwrap_err(),
+                ZkPoolError::ProposalAccountMismatch.into()
+            );
+        }
+    }
+
+    fn roots_with(capacity: u16, count: u8) -> RootsAccount {
+        let mut roots = RootsAccount {
+            version: ACCOUNT_VERSION,
+            roots: vec![RootEntry::default(); capacity as usize],
+            cursor: 0,
+            size: 0,
+            capacity,
+            next_seq: 0,
+            bump: 255,
+            tree_index: 0,
+            leaf_count: 0,
+            reserved: [0u8; ROOTS_RESERVED_LEN],
+        };
+        for i in 1..=count {
+            roots.add_root([i; 32], i as u64 * 10, 0).unwrap();
+        }
+        roots
+    }
+
+    fn ordered(roots: &RootsAccount) -> Vec<[u8; 32]> {
+        roots.ordered_roots().iter().map(|e| e.root).collect()
+    }
+
+    #[test]
+    fn test_roots_resize_keeps_ring_order() {
+        // Wrapped buffer: roots 3, 4, 5 in a window of 3 (1 and 2 evicted)
+        let mut roots = roots_with(3, 5);
+        assert_eq!(ordered(&roots), vec![[3; 32], [4; 32], [5; 32]]);
+
+        // Grow: order kept, next root lands after the newest
+        roots.resize(5);
+        assert_eq!(roots.roots.len(), 5);
+        assert_eq!(ordered(&roots), vec![[3; 32], [4; 32], [5; 32]]);
+        roots.add_root([6; 32], 60, 0).unwrap();
+        assert_eq!(ordered(&roots), vec![[3; 32], [4; 32], [5; 32], [6; 32]]);
+
+        // Shrink: oldest roots are dropped
+        roots.resize(2);
+        assert_eq!(roots.roots.len(), 2);
+        assert_eq!(ordered(&roots), vec![[5; 32], [6; 32]]);
+        assert!(!roots.contains_root(&[4; 32], 60, 0));
+        roots.add_root([7; 32], 70, 0).unwrap();
+        assert_eq!(ordered(&roots), vec![[6; 32], [7; 32]]);
+
+        // Sequence numbers survive resizing and keep increasing
+        let seqs: Vec<u64> = roots.ordered_roots().iter().map(|e| e.seq).collect();
+        assert_eq!(seqs, vec![5, 6]);
+        assert_eq!(roots.next_seq, 7);
+    }
+
+    #[test]
+    fn test_root_window_growth_is_stepped() {
+        // 192 new entries need more than one realloc allows
+        let step = (MAX_PERMITTED_DATA_INCREASE / RootEntry::LEN) as u16;
+        assert!(MAX_ROOT_WINDOW - DEFAULT_ROOT_WINDOW > step);
+        assert_eq!(
+            RootsAccount::checked_window(DEFAULT_ROOT_WINDOW, MAX_ROOT_WINDOW).unwrap_err(),
+            ZkPoolError::RootWindowGrowthTooLarge.into()
+        );
+
+        // The same target is reachable in two calls
+        let mid = DEFAULT_ROOT_WINDOW + step;
+        assert_eq!(
+            RootsAccount::checked_window(DEFAULT_ROOT_WINDOW, mid).unwrap(),
+            mid
+        );
+        assert_eq!(
+            RootsAccount::checked_window(mid, MAX_ROOT_WINDOW).unwrap(),
+            MAX_ROOT_WINDOW
+        );
+
+        // Shrinking is never limited, out-of-range windows still fail
+        assert!(RootsAccount::checked_window(MAX_ROOT_WINDOW, 1).is_ok());
+        assert_eq!(
+            RootsAccount::checked_window(1, 0).unwrap_err(),
+            ZkPoolError::InvalidRootWindow.into()
+        );
+        assert_eq!(
+            RootsAccount::checked_window(1, MAX_ROOT_WINDOW + 1).unwrap_err(),
+            ZkPoolError::InvalidRootWindow.into()
+        );
+    }
+
+    #[test]
+    fn test_contains_root_enforces_max_age() {
+        // Root n posted at slot n * 10
+        let roots = roots_with(4, 3);
+
+        assert!(roots.contains_root(&[1; 32], 1_000, 0));
+        assert!(roots.contains_root(&[1; 32], 15, 5));
+        assert!(!roots.contains_root(&[1; 32], 16, 5));
+        assert!(roots.contains_root(&[3; 32], 35, 5));
+        assert!(!roots.contains_root(&[9; 32], 30, 0));
+    }
+
+    #[test]
+    fn test_revoked_root_is_rejected() {
+        let mut roots = roots_with(4, 3);
+
+        let entry = roots.revoke_root(&[2; 32]).unwrap();
+        assert_eq!(entry.seq, 1);
+        assert!(entry.revoked);
+        assert!(!roots.contains_root(&[2; 32], 30, 0));
+        assert!(roots.contains_root(&[3; 32], 30, 0));
+
+        // Already revoked or unknown roots cannot be revoked
+        assert!(roots.revoke_root(&[2; 32]).is_err());
+        assert!(roots.revoke_root(&[9; 32]).is_err());
+
+        // Revocation survives resizing
+        roots.resize(8);
+        assert!(!roots.contains_root(&[2; 32], 30, 0));
+    }
+
+    #[test]
+    fn test_add_root_rejects_zero_and_duplicates() {
+        let mut roots = roots_with(4, 3);
+
+        assert_eq!(
+            roots.add_root([0; 32], 40, 0).unwrap_err(),
+            ZkPoolError::ZeroRoot.into()
+        );
+        assert_eq!(
+            roots.add_root([3; 32], 40, 0).unwrap_err(),
+            ZkPoolError::DuplicateRoot.into()
+        );
+        assert_eq!(
+            roots.add_root([1; 32], 40, 0).unwrap_err(),
+            ZkPoolError::RootAlreadyInWindow.into()
+        );
+
+        // Revoked roots still count as present
+        roots.revoke_root(&[2; 32]).unwrap();
+        assert_eq!(
+            roots.add_root([2; 32], 40, 0).unwrap_err(),
+            ZkPoolError::RootAlreadyInWindow.into()
+        );
+
+        // Rejections consume no sequence numbers
+        assert_eq!(roots.add_root([4; 32], 40, 0).unwrap(), 3);
+
+        // Once evicted, a root may be posted again
+        roots.add_root([5; 32], 50, 0).unwrap();
+        assert_eq!(roots.add_root([1; 32], 60, 0).unwrap(), 5);
+
+        let mut empty = roots_with(4, 0);
+        empty.capacity = 0;
+        assert_eq!(
+            empty.add_root([1; 32], 0, 0).unwrap_err(),
+            ZkPoolError::RootsNotInitialized.into()
+        );
+    }
+
+    #[test]
+    fn test_pending_root_counts_current_attesters_only() {
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+        let c = Pubkey::new_unique();
+        assert!(AttesterSet::validate(&[a, b], 2, 100).is_ok());
+        assert!(AttesterSet::validate(&[a, a], 1, 100).is_err());
+        assert!(AttesterSet::validate(&[a, b], 3, 100).is_err());
+        assert!(AttesterSet::validate(&[a, b], 1, 0).is_err());
+
+        let set = AttesterSet {
+            version: ACCOUNT_VERSION,
+            attesters: vec![a, b],
+            threshold: 2,
+            expiry_slots: 100,
+            bump: 255,
+            reserved: [0u8; ATTESTERS_RESERVED_LEN],
+        };
+        let pending = PendingRoot {
+            version: ACCOUNT_VERSION,
+            root: [7u8; 32],
+            attesters: vec![a, c],
+            first_slot: 1_000,
+            payer: a,
+            bump: 255,
+            tree_index: 0,
+            reserved: [0u8; PENDING_ROOT_RESERVED_LEN],
+        };
+
+        // c was removed from the set, so its attestation no longer counts
+        assert_eq!(pending.attestation_count(&set), 1);
+        assert!(!pending.is_expired(1_100, set.expiry_slots));
+        assert!(pending.is_expired(1_101, set.expiry_slots));
+    }
+
+    #[test]
+    fn test_tree_fills_and_seeds_keep_legacy_address() {
+        let mut roots = roots_with(4, 0);
+
+        // Depth 2 holds four leaves
+        for expected in 0..4 {
+            assert_eq!(roots.insert_leaf(2).unwrap(), expected);
+        }
+        assert!(roots.is_full(2));
+        assert_eq!(
+            roots.insert_leaf(2).unwrap_err(),
+            ZkPoolError::TreeFull.into()
+        );
+
+        // Tree 0 derives the same PDA as the original ["roots"] seeds
+        let (legacy, _) = Pubkey::find_program_address(&[ROOTS_SEED], &crate::ID);
+        let (tree0, _) =
+            Pubkey::find_program_address(&[ROOTS_SEED, tree_seed(0).as_slice()], &crate::ID);
+        let (tree1, _) =
+            Pubkey::find_program_address(&[ROOTS_SEED, tree_seed(1).as_slice()], &crate::ID);
+        assert_eq!(legacy, tree0);
+        assert_ne!(tree0, tree1);
+
+        // The tree index cannot wrap back onto tree 0
+        let mut config = test_config();
+        assert_eq!(config.next_tree().unwrap(), 1);
+        config.active_tree = u32::MAX;
+        assert_eq!(
+            config.next_tree().unwrap_err(),
+            ZkPoolError::ArithmeticOverflow.into()
+        );
+    }
+
+    #[test]
+    fn test_association_roots_window() {
+        let admin = Pubkey::new_unique();
+        let mut set = AssociationRoots {
+            version: ACCOUNT_VERSION,
+            curator: Pubkey::default(),
+            roots: Vec::new(),
+            cursor: 0,
+            bump: 255,
+            reserved: [0u8; ASSOCIATION_RESERVED_LEN],
+        };
+
+        // Without a curator only the admin manages the set
+        assert!(set.is_manager(&admin, &admin));
+        assert!(!set.is_manager(&Pubkey::default(), &admin));
+        let curator = Pubkey::new_unique();
+        set.curator = curator;
+        assert!(set.is_manager(&curator, &admin));
+
+        assert_eq!(
+            set.add_root([0u8; 32]).unwrap_err(),
+            ZkPoolError::ZeroRoot.into()
+        );
+        assert_eq!(set.add_root([1u8; 32]).unwrap(), 0);
+        assert_eq!(
+            set.add_root([1u8; 32]).unwrap_err(),
+            ZkPoolError::RootAlreadyInWindow.into()
+        );
+        assert!(set.contains_root(&[1u8; 32]));
+        assert!(!set.contains_root(&[0u8; 32]));
+
+        // Filling the window overwrites the oldest root
+        for i in 2..=(ASSOCIATION_ROOT_WINDOW as u8 + 1) {
+            set.add_root([i; 32]).unwrap();
+        }
+        assert_eq!(set.roots.len(), ASSOCIATION_ROOT_WINDOW);
+        assert!(!set.contains_root(&[1u8; 32]));
+        assert!(set.contains_root(&[ASSOCIATION_ROOT_WINDOW as u8 + 1; 32]));
+    }
+
+    #[test]
+    fn test_deny_list_sorted() {
+        let mut list = DenyList {
+            version: ACCOUNT_VERSION,
+            compliance: Pubkey::default(),
+            entries: Vec::new(),
+            bump: 255,
+            reserved: [0u8; DENY_LIST_RESERVED_LEN],
+        };
+
+        let keys: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        for key in keys.iter().rev() {
+            assert!(list.set_denied(*key, true).unwrap());
+        }
+        assert!(!list.set_denied(keys[0], true).unwrap());
+        assert!(list.entries.windows(2).all(|w| w[0] < w[1]));
+        assert!(keys.iter().all(|k| list.is_denied(k)));
+
+        assert!(list.set_denied(keys[2], false).unwrap());
+        assert!(!list.set_denied(keys[2], false).unwrap());
+        assert!(!list.is_denied(&keys[2]));
+        assert_eq!(list.entries.len(), 4);
+
+        while list.entries.len() < MAX_DENY_LIST_ENTRIES {
+            list.set_denied(Pubkey::new_unique(), true).unwrap();
+        }
+        assert_eq!(
+            list.set_denied(Pubkey::new_unique(), true).unwrap_err(),
+            ZkPoolError::DenyListFull.into()
+        );
+    }
+
+    #[test]
+    fn test_deposit_limits() {
+        let mut config = test_config();
+
+        // All limits disabled by default
+        config.record_shield(u64::MAX / 2).unwrap();
+        config.record_unshield(u64::MAX / 2).unwrap();
+        assert_eq!(config.tvl, 0);
+
+        config.max_shield_amount = 100;
+        config.max_unshield_amount = 50;
+        config.tvl_cap = 150;
+
+        assert_eq!(
+            config.record_shield(101).unwrap_err(),
+            ZkPoolError::ShieldLimitExceeded.into()
+        );
+        config.record_shield(100).unwrap();
+        assert_eq!(
+            config.record_shield(51).unwrap_err(),
+            ZkPoolError::TvlCapExceeded.into()
+        );
+        config.record_shield(50).unwrap();
+        assert_eq!(config.tvl, 150);
+
+        assert_eq!(
+            config.record_unshield(51).unwrap_err(),
+            ZkPoolError::UnshieldLimitExceeded.into()
+        );
+        config.record_unshield(50).unwrap();
+        assert_eq!(config.tvl, 100);
+
+        // Withdrawing more than the pool holds fails like the solvency check
+        config.max_unshield_amount = 0;
+        assert_eq!(
+            config.record_unshield(1_000).unwrap_err(),
+            ZkPoolError::TreasuryInsolvent.into()
+        );
+        assert_eq!(config.tvl, 100);
+    }
+
+    #[test]
+    fn test_fixed_denomination() {
+        let mut config = test_config();
+        config.denomination = 1_000_000_000;
+
+        assert_eq!(
+            config.record_shield(999_999_999).unwrap_err(),
+            ZkPoolError::InvalidDenomination.into()
+        );
+        config.record_shield(1_000_000_000).unwrap();
+
+        assert_eq!(
+            config.record_unshield(2_000_000_000).unwrap_err(),
+            ZkPoolError::InvalidDenomination.into()
+        );
+        config.record_unshield(1_000_000_000).unwrap();
+        assert_eq!(config.tvl, 0);
+    }
+
+    #[test]
+    fn test_withdrawal_delay() {
+        let mut config = test_config();
+        assert!(!config.requires_withdrawal_delay(u64::MAX));
+
+        config.withdrawal_delay_threshold = 1_000;
+        assert!(!config.requires_withdrawal_delay(1_000));
+        assert!(config.requires_withdrawal_delay(1_001));
+
+        let nullifier = [7u8; 32];
+        assert_eq!(
+            PendingWithdrawal::nullifier_seed(&[[1u8; 32], nullifier]),
+            nullifier
+        );
+        assert_eq!(PendingWithdrawal::nullifier_seed(&[[1u8; 32]]), [0u8; 32]);
+
+        let pending = PendingWithdrawal {
+            version: ACCOUNT_VERSION,
+            nullifier,
+            recipient: Pubkey::new_unique(),
+            amount: 5_000,
+            fee: 0,
+            association_root: [0u8; 32],
+            claimable_slot: 200,
+            payer: Pubkey::new_unique(),
+            bump: 255,
+            reserved: [0u8; PENDING_WITHDRAWAL_RESERVED_LEN],
+        };
+        assert!(!pending.is_claimable(199));
+        assert!(pending.is_claimable(200));
+    }
+
+    #[test]
+    fn test_payout_rent_minimum() {
+        let mut config = test_config();
+        let rent = 890_880;
+
+        // Existing accounts and empty payouts are unaffected
+        let payout = config.plan_payout(1_000, 100, rent, rent).unwrap();
+        assert_eq!(
+            (payout.paid, payout.retained_fee, payout.top_up),
+            (900, 100, 0)
+        );
+        let payout = config.plan_payout(100, 100, 0, rent).unwrap();
+        assert_eq!(payout.paid, 0);
+
+        // A small payout to a new account is rejected
+        assert_eq!(
+            config.plan_payout(500_000, 10_000, 0, rent).unwrap_err(),
+            ZkPoolError::RecipientBelowRentMinimum.into()
+        );
+
+        // Top-up comes out of the fee
+        config.top_up_recipients = true;
+        let payout = config.plan_payout(rent + 5_000, 10_000, 0, rent).unwrap();
+        assert_eq!(payout.paid, rent);
+        assert_eq!(payout.retained_fee, 5_000);
+        assert_eq!(payout.top_up, 5_000);
+
+        // ...but only up to the fee
+        assert_eq!(
+            config.plan_payout(500_000, 10_000, 0, rent).unwrap_err(),
+            ZkPoolError::RecipientBelowRentMinimum.into()
+        );
+    }
+
+    #[test]
+    fn test_protocol_fees() {
+        let mut config = test_config();
+        config.shield_fee_bps = 25;
+        config.unshield_fee_bps = 50;
+
+        assert_eq!(config.shield_protocol_fee(1_000_000), 2_500);
+        assert_eq!(config.shield_protocol_fee(399), 0); // rounds down
+        assert_eq!(config.unshield_protocol_fee(u64::MAX), u64::MAX / 200);
+
+        // The protocol fee comes out of the recipient's share
+        let payout = config.plan_payout(1_000_000, 1_000, 1, 0).unwrap();
+        assert_eq!(payout.protocol_fee, 5_000);
+        assert_eq!(payout.retained_fee, 1_000);
+        assert_eq!(payout.paid, 994_000);
+
+        assert_eq!(
+            config.plan_payout(1_000_000, 995_001, 1, 0).unwrap_err(),
+            ZkPoolError::FeeExceedsAmount.into()
+        );
+    }
+
+    #[test]
+    fn test_pool_state_solvency() {
+        let mut state = PoolState {
+            version: ACCOUNT_VERSION,
+            total_deposited: 0,
+            total_withdrawn: 0,
+            total_fees: 0,
+            expected_balance: 0,
+            bump: 255,
+            total_protocol_fees: 0,
+            reserved: [0u8; POOL_STATE_RESERVED_LEN],
+        };
+        let rent = 890_880;
+
+        state.record_deposit(1_000).unwrap();
+        state.check_solvency(rent + 1_000, rent).unwrap();
+        assert_eq!(
+            state.check_solvency(rent + 999, rent).unwrap_err(),
+            ZkPoolError::TreasuryInsolvent.into()
+        );
+
+        // The fee stays in the treasury
+        state.record_withdrawal(890, 100, 10).unwrap();
+        assert_eq!(state.expected_balance, 100);
+        assert_eq!(state.total_withdrawn, 890);
+        assert_eq!(state.total_fees, 100);
+        assert_eq!(state.total_protocol_fees, 10);
+        state.check_solvency(rent + 100, rent).unwrap();
+
+        assert_eq!(
+            state.record_withdrawal(100, 0, 1).unwrap_err(),
+            ZkPoolError::TreasuryInsolvent.into()
+        );
+    }
+
+    #[test]
+    fn test_treasury_rate_limit() {
+        let mut state = TreasuryState {
+            version: ACCOUNT_VERSION,
+            max_outflow: 0,
+            window_slots: 0,
+            outflow: 0,
+            last_slot: 0,
+            bump: 255,
+            reserved: [0u8; TREASURY_STATE_RESERVED_LEN],
+        };
+
+        // Disabled by default
+        state.record_outflow(u64::MAX, 10).unwrap();
+        assert_eq!(state.outflow, 0);
+
+        assert_eq!(
+            state.configure(1_000, 0, 10).unwrap_err(),
+            ZkPoolError::InvalidRateLimit.into()
+        );
+        state.configure(1_000, 100, 10).unwrap();
+
+        state.record_outflow(600, 10).unwrap();
+        state.record_outflow(400, 10).unwrap();
+        assert_eq!(
+            state.record_outflow(1, 10).unwrap_err(),
+            ZkPoolError::WithdrawalRateLimited.into()
+        );
+
+        // 10 lamports drain per slot
+        state.record_outflow(250, 35).unwrap();
+        assert_eq!(state.outflow, 1_000);
+
+        // A full window empties the bucket
+        state.record_outflow(1_000, 1_000).unwrap();
+        assert_eq!(state.outflow, 1_000);
+
+        // Disabling resets the level
+        state.configure(0, 0, 1_000).unwrap();
+        assert_eq!(state.outflow, 0);
+    }
+
+    #[test]
+    fn test_credential_validity() {
+        let issuer = Pubkey::new_unique();
+        let credential = Credential {
+            version: ACCOUNT_VERSION,
+            holder: Pubkey::new_unique(),
+            issuer,
+            expires_at: 1_000,
+            bump: 255,
+            reserved: [0u8; CREDENTIAL_RESERVED_LEN],
+        };
+
+        assert!(credential.is_valid(&issuer, 999));
+        assert!(!credential.is_valid(&issuer, 1_000));
+        assert!(!credential.is_valid(&Pubkey::new_unique(), 999));
+    }
+
+    #[test]
+    fn test_commitment_queue_batches() {
+        let mut queue = CommitmentQueue {
+            version: ACCOUNT_VERSION,
+            tree_index: 1,
+            current_root: [0u8; 32],
+            next_index: 0,
+            leaves: Vec::new(),
+            bump: 255,
+            reserved: [0u8; QUEUE_RESERVED_LEN],
+        };
+
+        for i in 1..=(BATCH_APPEND_SIZE as u8 + 2) {
+            queue.push([i; 32]).unwrap();
+        }
+
+        let (batch, count) = queue.next_batch(false).unwrap();
+        assert_eq!(count, BATCH_APPEND_SIZE);
+        assert_eq!(batch[0], [1; 32]);
+        queue.pop_batch(count, [9; 32]);
+        assert_eq!(queue.next_index, BATCH_APPEND_SIZE as u64);
+        assert_eq!(queue.current_root, [9; 32]);
+
+        // Two leaves left: only a retired tree may pad them into a batch
+        assert_eq!(
+            queue.next_batch(false).unwrap_err(),
+            ZkPoolError::BatchNotReady.into()
+        );
+        let (batch, count) = queue.next_batch(true).unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(batch[1], [BATCH_APPEND_SIZE as u8 + 2; 32]);
+        assert_eq!(batch[2], [0; 32]);
+
+        queue.pop_batch(count, [10; 32]);
+        assert!(queue.leaves.is_empty());
+        assert_eq!(queue.next_index, 2 * BATCH_APPEND_SIZE as u64);
+        assert!(queue.next_batch(true).is_err());
+    }
+}
@@ -1,6 +1,8 @@
 use crate::errors::ZkPoolError;
 use crate::state::VerificationKeyAccount;
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::AccountMeta;
+use sha2::{Digest, Sha256};
 
 // ============================================================================
 // ENCODING ADAPTER LAYER
@@ -97,6 +99,50 @@ pub fn verify_proof(
     Ok(())
 }
 
+/// Field element binding an encrypted note payload to a proof
+///
+/// SHA-256 of the payload with the top 3 bits cleared so it fits in BN254,
+/// read as a LITTLE-ENDIAN field element (matches ABI.md `ciphertext_hash`)
+pub fn ciphertext_hash(encrypted_output: &[u8]) -> [u8; 32] {
+    let mut hash: [u8; 32] = Sha256::digest(encrypted_output).into();
+    hash[31] &= 0x1f;
+    hash
+}
+
+/// Field element binding a post-withdrawal call to an unshield proof
+///
+/// SHA-256 of the target program id, the ordered account metas and the
+/// instruction data, reduced like `ciphertext_hash` (matches ABI.md `call_hash`).
+/// The account count is hashed first so metas and data cannot be re-split.
+pub fn call_hash(program_id: &Pubkey, accounts: &[AccountMeta], data: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(program_id.as_ref());
+    hasher.update((accounts.len() as u32).to_le_bytes());
+    for meta in accounts {
+        hasher.update(meta.pubkey.as_ref());
+        hasher.update([meta.is_signer as u8, meta.is_writable as u8]);
+    }
+    hasher.update(data);
+    let mut hash: [u8; 32] = hasher.finalize().into();
+    hash[31] &= 0x1f;
+    hash
+}
+
+/// Convert field element bytes to u64 (assuming little-endian encoding)
+pub fn field_to_u64(field: &[u8; 32]) -> Result<u64> {
+    // Take first 8 bytes as little-endian u64
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&field[..8]);
+    let value = u64::from_le_bytes(bytes);
+
+    // Verify remaining bytes are zero (amount must fit in u64)
+    for &b in &field[8..] {
+        require!(b == 0, ZkPoolError::AmountTooLarge);
+    }
+
+    Ok(value)
+}
+
 /// Parse proof bytes into Groth16Proof structure
 /// Expected format: A (64 bytes) + B (128 bytes) + C (64 bytes) = 256 bytes
 fn parse_proof(proof_bytes: &[u8]) -> Result<Groth16Proof> {
@@ -431,6 +477,51 @@ mod tests {
         assert_eq!(le_original, le_back);
     }
 
+    #[test]
+    fn test_ciphertext_hash_matches_test_vectors() {
+        // ciphertext_hash of b"noirwire-test-ciphertext" in test_vectors/*_input.json
+        let expected: [u8; 32] = [
+            0xdd, 0xfa, 0x64, 0xd3, 0xa8, 0xe0, 0x32, 0x8d, 0xbf, 0x6a, 0xef, 0x74, 0x3b, 0x54,
+            0x1d, 0x47, 0x1a, 0x6d, 0x7b, 0xcd, 0xd1, 0xf0, 0x38, 0x63, 0xb4, 0xa2, 0x06, 0x59,
+            0x35, 0x5d, 0x58, 0x08,
+        ];
+        let hash = ciphertext_hash(b"noirwire-test-ciphertext");
+        assert_eq!(hash, expected);
+        assert!(hash[31] < 0x30);
+    }
+
+    #[test]
+    fn test_call_hash_binds_program_accounts_and_data() {
+        let program = Pubkey::new_from_array([7u8; 32]);
+        let account = Pubkey::new_from_array([9u8; 32]);
+        let metas = [AccountMeta::new(account, false)];
+        let hash = call_hash(&program, &metas, b"deposit");
+
+        // Same bytes as hashing the concatenation, reduced into the field
+        let mut joined = program.to_bytes().to_vec();
+        joined.extend_from_slice(&1u32.to_le_bytes());
+        joined.extend_from_slice(account.as_ref());
+        joined.extend_from_slice(&[0, 1]);
+        joined.extend_from_slice(b"deposit");
+        assert_eq!(hash, ciphertext_hash(&joined));
+        assert!(hash[31] < 0x20);
+
+        assert_ne!(hash, call_hash(&program, &metas, b"withdraw"));
+        assert_ne!(
+            hash,
+            call_hash(&Pubkey::new_from_array([8u8; 32]), &metas, b"deposit")
+        );
+        assert_ne!(
+            hash,
+            call_hash(
+                &program,
+                &[AccountMeta::new_readonly(account, false)],
+                b"deposit"
+            )
+        );
+        assert_ne!(hash, call_hash(&program, &[], b"deposit"));
+    }
+
     #[test]
     fn test_g1_endianness_conversion() {
         let le_point = G1Point {
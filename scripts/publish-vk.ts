@@ -61,6 +61,7 @@ async function publishVk(
       config: configPda,
       vkAccount: vkPda,
       admin: admin.publicKey,
+      payer: admin.publicKey,
       systemProgram: anchor.web3.SystemProgram.programId,
     })
     .signers([admin])
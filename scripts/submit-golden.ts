@@ -11,6 +11,9 @@ import * as path from "path";
 
 const ZK_CIRCUITS_DIR = path.join(__dirname, "../zk-circuits");
 
+// Note ciphertext the golden proofs commit to via ciphertext_hash
+const ENCRYPTED_OUTPUT = Buffer.from("noirwire-test-ciphertext");
+
 const CIRCUITS: { [key: string]: number } = {
   shield: 0,
   transfer: 1,
@@ -85,12 +88,39 @@ async function submitShield(
     program.programId
   );
 
+  const [rootsPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("roots")],
+    program.programId
+  );
+
+  const [queuePda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("commitment_queue"), Buffer.alloc(4, 0)],
+    program.programId
+  );
+
+  const [treasuryPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("treasury")],
+    program.programId
+  );
+
+  const [poolStatePda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("pool_state")],
+    program.programId
+  );
+
   const tx = await program.methods
-    .submitShield(Array.from(proofBytes), publicInputs)
+    .submitShield(Array.from(proofBytes), publicInputs, ENCRYPTED_OUTPUT)
     .accounts({
       config: configPda,
       vkAccount: vkPda,
+      roots: rootsPda,
+      queue: queuePda,
+      credential: null, // pool is not permissioned
+      treasury: treasuryPda,
+      poolState: poolStatePda,
+      feeVault: null, // no protocol fee configured
       user: user.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
     })
     .signers([user])
     .rpc();
@@ -140,6 +170,11 @@ async function submitTransfer(
     program.programId
   );
 
+  const [queuePda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("commitment_queue"), Buffer.alloc(4, 0)],
+    program.programId
+  );
+
   const shard = Buffer.alloc(2, 0);
   const [nullifiersPda] = anchor.web3.PublicKey.findProgramAddressSync(
     [Buffer.from("nullifiers"), shard],
@@ -153,20 +188,20 @@ async function submitTransfer(
     .accounts({
       config: configPda,
       roots: rootsPda,
-      authority: user.publicKey,
       admin: user.publicKey,
-      systemProgram: anchor.web3.SystemProgram.programId,
     })
     .signers([user])
     .rpc();
 
   // Submit transfer
   const tx = await program.methods
-    .submitTransfer(Array.from(proofBytes), publicInputs)
+    .submitTransfer(Array.from(proofBytes), publicInputs, 0, ENCRYPTED_OUTPUT)
     .accounts({
       config: configPda,
       vkAccount: vkPda,
       roots: rootsPda,
+      activeRoots: rootsPda,
+      queue: queuePda,
       nullifiers: nullifiersPda,
       user: user.publicKey,
       systemProgram: anchor.web3.SystemProgram.programId,
@@ -231,6 +266,23 @@ async function submitUnshield(
     program.programId
   );
 
+  const [poolStatePda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("pool_state")],
+    program.programId
+  );
+
+  // Rate limiter PDA (may not exist yet, then outflow is unlimited)
+  const [treasuryStatePda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("treasury_state")],
+    program.programId
+  );
+
+  // Deny-list PDA (may not exist yet, then nothing is denied)
+  const [denyListPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("deny_list")],
+    program.programId
+  );
+
   const shard = Buffer.alloc(2, 0);
   const [nullifiersPda] = anchor.web3.PublicKey.findProgramAddressSync(
     [Buffer.from("nullifiers"), shard],
@@ -244,22 +296,25 @@ async function submitUnshield(
     .accounts({
       config: configPda,
       roots: rootsPda,
-      authority: user.publicKey,
       admin: user.publicKey,
-      systemProgram: anchor.web3.SystemProgram.programId,
     })
     .signers([user])
     .rpc();
 
   // Submit unshield
   const tx = await program.methods
-    .submitUnshield(Array.from(proofBytes), publicInputs)
+    .submitUnshield(Array.from(proofBytes), publicInputs, 0)
     .accounts({
       config: configPda,
       vkAccount: vkPda,
       roots: rootsPda,
       nullifiers: nullifiersPda,
+      associationRoots: null, // golden proof opts out (association_root = 0)
+      denyList: denyListPda,
       treasury: treasuryPda,
+      treasuryState: treasuryStatePda,
+      poolState: poolStatePda,
+      feeVault: null, // no protocol fee configured
       recipient: recipient,
       user: user.publicKey,
       systemProgram: anchor.web3.SystemProgram.programId,
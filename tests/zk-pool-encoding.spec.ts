@@ -250,37 +250,49 @@ describe("ZK Pool Encoding & Negative Tests", () => {
   });
 
   describe("Public Input Ordering", () => {
-    it("should validate shield public inputs (1 input)", () => {
+    it("should validate shield public inputs (3 inputs)", () => {
       const commitment = Buffer.alloc(32);
       commitment.fill(0x42);
+      const amount = Buffer.alloc(32, 0x00);
+      amount.writeBigUInt64BE(BigInt(1_000_000), 24);
+      const ciphertextHash = Buffer.alloc(32, 0x05);
 
-      const publicInputs = [commitment];
-      expect(publicInputs.length).to.equal(1);
+      const publicInputs = [commitment, amount, ciphertextHash];
+      expect(publicInputs.length).to.equal(3);
+
+      // Verify ordering
+      expect(publicInputs[0][0]).to.equal(0x42); // commitment
+      expect(publicInputs[1].readBigUInt64BE(24)).to.equal(BigInt(1_000_000)); // amount
+      expect(publicInputs[2][0]).to.equal(0x05); // ciphertext_hash
     });
 
-    it("should validate transfer public inputs (4 inputs)", () => {
+    it("should validate transfer public inputs (5 inputs)", () => {
       const root = Buffer.alloc(32, 0x01);
       const nullifier = Buffer.alloc(32, 0x02);
       const commitment = Buffer.alloc(32, 0x03);
       const fee = Buffer.alloc(32, 0x04);
+      const ciphertextHash = Buffer.alloc(32, 0x05);
 
-      const publicInputs = [root, nullifier, commitment, fee];
-      expect(publicInputs.length).to.equal(4);
+      const publicInputs = [root, nullifier, commitment, fee, ciphertextHash];
+      expect(publicInputs.length).to.equal(5);
 
       // Verify ordering
       expect(publicInputs[0][0]).to.equal(0x01); // root
       expect(publicInputs[1][0]).to.equal(0x02); // nullifier
       expect(publicInputs[2][0]).to.equal(0x03); // commitment
       expect(publicInputs[3][0]).to.equal(0x04); // fee
+      expect(publicInputs[4][0]).to.equal(0x05); // ciphertext_hash
     });
 
-    it("should validate unshield public inputs (6 inputs)", () => {
+    it("should validate unshield public inputs (8 inputs)", () => {
       const root = Buffer.alloc(32, 0x01);
       const nullifier = Buffer.alloc(32, 0x02);
       const recipient_lo = Buffer.alloc(32, 0x03);
       const recipient_hi = Buffer.alloc(32, 0x04);
       const amount = Buffer.alloc(32, 0x05);
       const fee = Buffer.alloc(32, 0x06);
+      const association_root = Buffer.alloc(32, 0x07);
+      const call_hash = Buffer.alloc(32, 0x08);
 
       const publicInputs = [
         root,
@@ -289,8 +301,10 @@ describe("ZK Pool Encoding & Negative Tests", () => {
         recipient_hi,
         amount,
         fee,
+        association_root,
+        call_hash,
       ];
-      expect(publicInputs.length).to.equal(6);
+      expect(publicInputs.length).to.equal(8);
 
       // Verify ordering per ABI.md
       expect(publicInputs[0][0]).to.equal(0x01); // root
@@ -299,6 +313,8 @@ describe("ZK Pool Encoding & Negative Tests", () => {
       expect(publicInputs[3][0]).to.equal(0x04); // recipient_hi
       expect(publicInputs[4][0]).to.equal(0x05); // amount
       expect(publicInputs[5][0]).to.equal(0x06); // fee
+      expect(publicInputs[6][0]).to.equal(0x07); // association_root
+      expect(publicInputs[7][0]).to.equal(0x08); // call_hash
     });
 
     it("should reject swapped public inputs", () => {
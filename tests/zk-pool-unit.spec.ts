@@ -176,40 +176,53 @@ describe("ZK Pool Helper Functions", () => {
   });
 
   describe("Public Input Validation", () => {
-    it("should validate shield public inputs (1 input)", () => {
+    it("should validate shield public inputs (3 inputs)", () => {
       const commitment = Buffer.alloc(32, 0x42);
-      const publicInputs = [Array.from(commitment)];
+      const amount = Buffer.alloc(32, 0);
+      amount.writeBigUInt64LE(BigInt(1000));
+      const ciphertextHash = Buffer.alloc(32, 0x05);
+      const publicInputs = [
+        Array.from(commitment),
+        Array.from(amount),
+        Array.from(ciphertextHash),
+      ];
 
-      expect(publicInputs.length).to.equal(1);
-      expect(publicInputs[0].length).to.equal(32);
+      expect(publicInputs.length).to.equal(3);
+      publicInputs.forEach((input) => {
+        expect(input.length).to.equal(32);
+      });
     });
 
-    it("should validate transfer public inputs (4 inputs)", () => {
+    it("should validate transfer public inputs (5 inputs)", () => {
       const root = Buffer.alloc(32, 1);
       const nullifier = Buffer.alloc(32, 2);
       const newCommitment = Buffer.alloc(32, 3);
       const fee = Buffer.alloc(32, 0);
+      const ciphertextHash = Buffer.alloc(32, 5);
 
       const publicInputs = [
         Array.from(root),
         Array.from(nullifier),
         Array.from(newCommitment),
         Array.from(fee),
+        Array.from(ciphertextHash),
       ];
 
-      expect(publicInputs.length).to.equal(4);
+      expect(publicInputs.length).to.equal(5);
       publicInputs.forEach((input) => {
         expect(input.length).to.equal(32);
       });
     });
 
-    it("should validate unshield public inputs (6 inputs)", () => {
+    it("should validate unshield public inputs (8 inputs)", () => {
       const root = Buffer.alloc(32, 1);
       const nullifier = Buffer.alloc(32, 2);
       const recipientLo = Buffer.alloc(32, 3);
       const recipientHi = Buffer.alloc(32, 4);
       const amount = Buffer.alloc(32, 5);
       const fee = Buffer.alloc(32, 0);
+      const associationRoot = Buffer.alloc(32, 0); // opted out
+      const callHash = Buffer.alloc(32, 0); // no call
 
       const publicInputs = [
         Array.from(root),
@@ -218,9 +231,11 @@ describe("ZK Pool Helper Functions", () => {
         Array.from(recipientHi),
         Array.from(amount),
         Array.from(fee),
+        Array.from(associationRoot),
+        Array.from(callHash),
       ];
 
-      expect(publicInputs.length).to.equal(6);
+      expect(publicInputs.length).to.equal(8);
       publicInputs.forEach((input) => {
         expect(input.length).to.equal(32);
       });
@@ -311,13 +326,13 @@ describe("ZK Pool Helper Functions", () => {
     });
 
     it("should have correct public input counts", () => {
-      const SHIELD_PUBLIC_INPUTS = 1;
-      const TRANSFER_PUBLIC_INPUTS = 4;
-      const UNSHIELD_PUBLIC_INPUTS = 6;
+      const SHIELD_PUBLIC_INPUTS = 3;
+      const TRANSFER_PUBLIC_INPUTS = 5;
+      const UNSHIELD_PUBLIC_INPUTS = 8;
 
-      expect(SHIELD_PUBLIC_INPUTS).to.equal(1);
-      expect(TRANSFER_PUBLIC_INPUTS).to.equal(4);
-      expect(UNSHIELD_PUBLIC_INPUTS).to.equal(6);
+      expect(SHIELD_PUBLIC_INPUTS).to.equal(3);
+      expect(TRANSFER_PUBLIC_INPUTS).to.equal(5);
+      expect(UNSHIELD_PUBLIC_INPUTS).to.equal(8);
     });
 
     it("should have valid merkle depth range", () => {
@@ -19,8 +19,10 @@ describe("zk-pool", () => {
   let configBump: number;
   let rootsPda: anchor.web3.PublicKey;
   let rootsBump: number;
+  let queuePda: anchor.web3.PublicKey;
   let treasuryPda: anchor.web3.PublicKey;
   let treasuryBump: number;
+  let poolStatePda: anchor.web3.PublicKey;
 
   // Circuit VK PDAs
   let shieldVkPda: anchor.web3.PublicKey;
@@ -31,6 +33,8 @@ describe("zk-pool", () => {
   const MERKLE_DEPTH = 20;
   const ROOT_WINDOW = 64;
   const ABI_HASH = Buffer.alloc(32, 1); // Mock ABI hash for testing
+  // Note ciphertext the golden proofs commit to via ciphertext_hash
+  const ENCRYPTED_OUTPUT = Buffer.from("noirwire-test-ciphertext");
 
   before(async () => {
     // Derive PDAs
@@ -44,11 +48,22 @@ describe("zk-pool", () => {
       program.programId
     );
 
+    // Commitment queue of tree 0
+    [queuePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("commitment_queue"), Buffer.alloc(4, 0)],
+      program.programId
+    );
+
     [treasuryPda, treasuryBump] = anchor.web3.PublicKey.findProgramAddressSync(
       [Buffer.from("treasury")],
       program.programId
     );
 
+    [poolStatePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool_state")],
+      program.programId
+    );
+
     [shieldVkPda] = anchor.web3.PublicKey.findProgramAddressSync(
       [Buffer.from("vk"), Buffer.from([0])],
       program.programId
@@ -74,6 +89,7 @@ describe("zk-pool", () => {
       .initialize(MERKLE_DEPTH, ROOT_WINDOW, Array.from(ABI_HASH))
       .accounts({
         config: configPda,
+        queue: queuePda,
         admin: admin.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -107,6 +123,7 @@ describe("zk-pool", () => {
         config: configPda,
         vkAccount: shieldVkPda,
         admin: admin.publicKey,
+        payer: admin.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
@@ -118,7 +135,7 @@ describe("zk-pool", () => {
       shieldVkPda
     );
     expect(vkAccount.circuit).to.equal(0);
-    expect(vkAccount.nPublic).to.equal(1);
+    expect(vkAccount.nPublic).to.equal(3);
   });
 
   it("Sets verification key for transfer circuit", async () => {
@@ -142,6 +159,7 @@ describe("zk-pool", () => {
         config: configPda,
         vkAccount: transferVkPda,
         admin: admin.publicKey,
+        payer: admin.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
@@ -152,7 +170,7 @@ describe("zk-pool", () => {
       transferVkPda
     );
     expect(vkAccount.circuit).to.equal(1);
-    expect(vkAccount.nPublic).to.equal(4);
+    expect(vkAccount.nPublic).to.equal(5);
   });
 
   it("Sets verification key for unshield circuit", async () => {
@@ -176,6 +194,7 @@ describe("zk-pool", () => {
         config: configPda,
         vkAccount: unshieldVkPda,
         admin: admin.publicKey,
+        payer: admin.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
@@ -186,7 +205,7 @@ describe("zk-pool", () => {
       unshieldVkPda
     );
     expect(vkAccount.circuit).to.equal(2);
-    expect(vkAccount.nPublic).to.equal(6);
+    expect(vkAccount.nPublic).to.equal(8);
   });
 
   it("Adds a Merkle root", async () => {
@@ -197,9 +216,7 @@ describe("zk-pool", () => {
       .accounts({
         config: configPda,
         roots: rootsPda,
-        authority: admin.publicKey,
         admin: admin.publicKey,
-        systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
 
@@ -210,6 +227,52 @@ describe("zk-pool", () => {
     expect(Buffer.from(roots.roots[0])).to.deep.equal(testRoot);
   });
 
+  it("Rejects a root from a non-admin signer", async () => {
+    const outsider = anchor.web3.Keypair.generate();
+
+    try {
+      await program.methods
+        .addRoot(Array.from(Buffer.alloc(32, 0x43)))
+        .accounts({
+          config: configPda,
+          roots: rootsPda,
+          admin: outsider.publicKey,
+        })
+        .signers([outsider])
+        .rpc();
+
+      expect.fail("Should have failed with unauthorized error");
+    } catch (err) {
+      expect(err.toString()).to.include("Unauthorized");
+    }
+  });
+
+  it("Rejects add_root while a root signer is set", async () => {
+    const setRootSigner = (rootSigner: anchor.web3.PublicKey) =>
+      program.methods
+        .setRootSigner(rootSigner)
+        .accounts({ config: configPda, admin: admin.publicKey })
+        .rpc();
+
+    await setRootSigner(anchor.web3.Keypair.generate().publicKey);
+    try {
+      await program.methods
+        .addRoot(Array.from(Buffer.alloc(32, 0x44)))
+        .accounts({
+          config: configPda,
+          roots: rootsPda,
+          admin: admin.publicKey,
+        })
+        .rpc();
+
+      expect.fail("Should have failed with signed root required error");
+    } catch (err) {
+      expect(err.toString()).to.include("SignedRootRequired");
+    } finally {
+      await setRootSigner(anchor.web3.PublicKey.default);
+    }
+  });
+
   it("Submits a shield proof", async () => {
     const proofPath = path.join(
       __dirname,
@@ -234,16 +297,23 @@ describe("zk-pool", () => {
 
     // Validate public input count
     expect(publicInputs.length).to.equal(
-      1,
-      "Shield expects 1 public input (commitment)"
+      3,
+      "Shield expects 3 public inputs (commitment, amount, ciphertext_hash)"
     );
 
     const tx = await program.methods
-      .submitShield(Array.from(proofBytes), publicInputs)
+      .submitShield(Array.from(proofBytes), publicInputs, ENCRYPTED_OUTPUT)
       .accounts({
         config: configPda,
         vkAccount: shieldVkPda,
+        roots: rootsPda,
+        queue: queuePda,
+        credential: null, // pool is not permissioned
+        treasury: treasuryPda,
+        poolState: poolStatePda,
+        feeVault: null, // no protocol fee configured
         user: admin.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
 
@@ -276,8 +346,8 @@ describe("zk-pool", () => {
 
     // Validate public input count
     expect(publicInputs.length).to.equal(
-      4,
-      "Transfer expects 4 public inputs (root, nullifier, new_commitment, fee)"
+      5,
+      "Transfer expects 5 public inputs (root, nullifier, new_commitment, fee, ciphertext_hash)"
     );
 
     // First add the root from the proof
@@ -287,9 +357,7 @@ describe("zk-pool", () => {
       .accounts({
         config: configPda,
         roots: rootsPda,
-        authority: admin.publicKey,
         admin: admin.publicKey,
-        systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
 
@@ -302,11 +370,18 @@ describe("zk-pool", () => {
     );
 
     const tx = await program.methods
-      .submitTransfer(Array.from(proofBytes), publicInputs)
+      .submitTransfer(
+          Array.from(proofBytes),
+          publicInputs,
+          0,
+          ENCRYPTED_OUTPUT
+        )
       .accounts({
         config: configPda,
         vkAccount: transferVkPda,
         roots: rootsPda,
+        activeRoots: rootsPda,
+        queue: queuePda,
         nullifiers: nullifiersPda,
         user: admin.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -348,11 +423,18 @@ describe("zk-pool", () => {
     // Try to submit the same proof again
     try {
       await program.methods
-        .submitTransfer(Array.from(proofBytes), publicInputs)
+        .submitTransfer(
+          Array.from(proofBytes),
+          publicInputs,
+          0,
+          ENCRYPTED_OUTPUT
+        )
         .accounts({
           config: configPda,
           vkAccount: transferVkPda,
           roots: rootsPda,
+          activeRoots: rootsPda,
+          queue: queuePda,
           nullifiers: nullifiersPda,
           user: admin.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
@@ -392,8 +474,8 @@ describe("zk-pool", () => {
 
     // Validate public input count
     expect(publicInputs.length).to.equal(
-      6,
-      "Unshield expects 6 public inputs (root, nullifier, recipient_lo, recipient_hi, amount, fee)"
+      8,
+      "Unshield expects 8 public inputs (root, nullifier, recipient_lo, recipient_hi, amount, fee, association_root, call_hash)"
     );
 
     // First add the root
@@ -403,9 +485,7 @@ describe("zk-pool", () => {
       .accounts({
         config: configPda,
         roots: rootsPda,
-        authority: admin.publicKey,
         admin: admin.publicKey,
-        systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
 
@@ -420,14 +500,29 @@ describe("zk-pool", () => {
       program.programId
     );
 
+    const [denyListPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("deny_list")],
+      program.programId
+    );
+
+    const [treasuryStatePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury_state")],
+      program.programId
+    );
+
     const tx = await program.methods
-      .submitUnshield(Array.from(proofBytes), publicInputs)
+      .submitUnshield(Array.from(proofBytes), publicInputs, 0)
       .accounts({
         config: configPda,
         vkAccount: unshieldVkPda,
         roots: rootsPda,
         nullifiers: nullifiersPda,
+        associationRoots: null, // golden proof opts out (association_root = 0)
+        denyList: denyListPda,
         treasury: treasuryPda,
+        treasuryState: treasuryStatePda,
+        poolState: poolStatePda,
+        feeVault: null, // no protocol fee configured
         recipient: recipient,
         user: admin.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -436,6 +531,241 @@ describe("zk-pool", () => {
 
     console.log("✅ Submit unshield tx:", tx);
   });
+
+  it("Reports pool stats", async () => {
+    const stats = await program.methods
+      .getPoolStats()
+      .accounts({
+        config: configPda,
+        poolState: poolStatePda,
+        treasury: treasuryPda,
+      })
+      .view();
+
+    // The treasury always covers what the pool owes plus its rent
+    expect(stats.treasuryBalance.toNumber()).to.be.at.least(
+      stats.expectedBalance.toNumber() + stats.rentExemptMinimum.toNumber()
+    );
+    expect(stats.expectedBalance.toNumber()).to.equal(
+      stats.totalDeposited.toNumber() -
+        stats.totalWithdrawn.toNumber() -
+        stats.totalProtocolFees.toNumber()
+    );
+  });
+
+  // Runs last: hands admin rights to the multisig for the rest of the session
+  describe("Admin multisig", () => {
+    const member1 = anchor.web3.Keypair.generate();
+    const member2 = anchor.web3.Keypair.generate();
+    const outsider = anchor.web3.Keypair.generate();
+    let multisigPda: anchor.web3.PublicKey;
+
+    const proposalPda = (index: number) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("proposal"),
+          new anchor.BN(index).toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    // Pause bit of the shield circuit
+    const PAUSE_SHIELD = 1 << 0;
+
+    const pauseIx = (paused: boolean, adminKey: anchor.web3.PublicKey) =>
+      program.methods
+        .setPaused(PAUSE_SHIELD, paused)
+        .accounts({ config: configPda, admin: adminKey })
+        .instruction();
+
+    // Proposes set_paused with the given admin account, returning its index
+    const propose = async (
+      paused: boolean,
+      adminKey: anchor.web3.PublicKey
+    ) => {
+      const ix = await pauseIx(paused, adminKey);
+      const multisig = await program.account.multisig.fetch(multisigPda);
+      const index = multisig.proposalCount.toNumber();
+
+      await program.methods
+        .createProposal(
+          ix.keys.map((k) => ({
+            pubkey: k.pubkey,
+            isSigner: k.isSigner,
+            isWritable: k.isWritable,
+          })),
+          ix.data
+        )
+        .accounts({
+          multisig: multisigPda,
+          proposal: proposalPda(index),
+          proposer: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      return { index, keys: ix.keys };
+    };
+
+    const approve = (index: number, signer: anchor.web3.Keypair) =>
+      program.methods
+        .approveProposal()
+        .accounts({
+          multisig: multisigPda,
+          proposal: proposalPda(index),
+          signer: signer.publicKey,
+        })
+        .signers([signer])
+        .rpc();
+
+    const execute = (
+      index: number,
+      keys: anchor.web3.AccountMeta[],
+      signers: anchor.web3.Keypair[] = []
+    ) =>
+      program.methods
+        .executeProposal()
+        .accounts({
+          multisig: multisigPda,
+          proposal: proposalPda(index),
+          zkPoolProgram: program.programId,
+          executor: admin.publicKey,
+        })
+        .remainingAccounts(
+          keys.map((k) => ({
+            pubkey: k.pubkey,
+            isSigner: signers.some((s) => s.publicKey.equals(k.pubkey)),
+            isWritable: k.isWritable,
+          }))
+        )
+        .signers(signers)
+        .rpc();
+
+    const expectError = async (promise: Promise<unknown>, code: string) => {
+      try {
+        await promise;
+      } catch (err) {
+        const logs = (err.logs ?? []).join("\n");
+        expect(`${err}\n${logs}`).to.include(code);
+        return;
+      }
+      expect.fail(`Expected ${code}`);
+    };
+
+    before(async () => {
+      [multisigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("multisig")],
+        program.programId
+      );
+
+      for (const kp of [member1, member2, outsider]) {
+        const sig = await provider.connection.requestAirdrop(
+          kp.publicKey,
+          anchor.web3.LAMPORTS_PER_SOL
+        );
+        await provider.connection.confirmTransaction(sig);
+      }
+
+      // 2-of-3 multisig becomes the admin
+      await program.methods
+        .createMultisig(
+          [admin.publicKey, member1.publicKey, member2.publicKey],
+          2
+        )
+        .accounts({
+          config: configPda,
+          multisig: multisigPda,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      const config = await program.account.poolConfig.fetch(configPda);
+      expect(config.admin.toBase58()).to.equal(multisigPda.toBase58());
+    });
+
+    it("Rejects proposals and approvals from non-members", async () => {
+      const ix = await pauseIx(false, multisigPda);
+      const multisig = await program.account.multisig.fetch(multisigPda);
+
+      await expectError(
+        program.methods
+          .createProposal(
+            ix.keys.map((k) => ({
+              pubkey: k.pubkey,
+              isSigner: k.isSigner,
+              isWritable: k.isWritable,
+            })),
+            ix.data
+          )
+          .accounts({
+            multisig: multisigPda,
+            proposal: proposalPda(multisig.proposalCount.toNumber()),
+            proposer: outsider.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([outsider])
+          .rpc(),
+        "NotMultisigSigner"
+      );
+
+      const { index } = await propose(false, multisigPda);
+      await expectError(approve(index, outsider), "NotMultisigSigner");
+    });
+
+    it("Executes only at the threshold, once", async () => {
+      const { index, keys } = await propose(true, multisigPda);
+
+      // The proposer's own approval is 1 of 2
+      await expectError(execute(index, keys), "ProposalThresholdNotMet");
+
+      // A second approval from the proposer does not count
+      await expectError(
+        program.methods
+          .approveProposal()
+          .accounts({
+            multisig: multisigPda,
+            proposal: proposalPda(index),
+            signer: admin.publicKey,
+          })
+          .rpc(),
+        "ProposalAlreadyApproved"
+      );
+
+      await approve(index, member1);
+      await execute(index, keys);
+
+      const config = await program.account.poolConfig.fetch(configPda);
+      expect(config.pausedMask).to.equal(PAUSE_SHIELD);
+
+      await expectError(execute(index, keys), "ProposalAlreadyExecuted");
+      await expectError(approve(index, member2), "ProposalAlreadyExecuted");
+    });
+
+    it("Rejects a self-CPI carrying a forged admin", async () => {
+      // Approved proposal naming a key other than the multisig as admin: even
+      // when that key co-signs the execution, has_one rejects it inside the CPI
+      const forged = await propose(false, outsider.publicKey);
+      await approve(forged.index, member1);
+      await expectError(
+        execute(forged.index, forged.keys, [outsider]),
+        "Unauthorized"
+      );
+
+      // Approved proposal executed with a different admin account passed in
+      const { index, keys } = await propose(false, multisigPda);
+      await approve(index, member1);
+      const swapped = keys.map((k) =>
+        k.pubkey.equals(multisigPda) ? { ...k, pubkey: outsider.publicKey } : k
+      );
+      await expectError(
+        execute(index, swapped, [outsider]),
+        "ProposalAccountMismatch"
+      );
+
+      const config = await program.account.poolConfig.fetch(configPda);
+      expect(config.pausedMask).to.equal(PAUSE_SHIELD);
+    });
+  });
 });
 
 // ============================================================================
@@ -206,7 +206,7 @@ function main() {
         log(`📁 Created directory: ${OUTPUT_DIR}\n`);
     }
     
-    const circuits = ['shield', 'transfer', 'unshield'];
+    const circuits = ['shield', 'transfer', 'unshield', 'batch_append', 'disclosure'];
     const exported = {};
     
     // Export each circuit
@@ -3,7 +3,8 @@
 /**
  * make_vectors.js
  * 
- * Generates coherent test vectors for shield, transfer, and unshield circuits.
+ * Generates coherent test vectors for shield, transfer, unshield, batch_append and
+ * disclosure circuits.
  * Uses circomlibjs Poseidon to compute commitments and build a test Merkle tree.
  * 
  * This script is portable and works on any platform with Node.js installed.
@@ -11,6 +12,7 @@
  */
 
 const { buildPoseidon } = require('circomlibjs');
+const crypto = require('crypto');
 const fs = require('fs');
 const path = require('path');
 
@@ -19,6 +21,16 @@ const SCRIPT_DIR = __dirname;
 const PROJECT_ROOT = path.resolve(SCRIPT_DIR, '..');
 const TEST_VECTORS_DIR = path.join(PROJECT_ROOT, 'test_vectors');
 
+// Sample encrypted note payload; tests submit the same bytes on-chain
+const TEST_ENCRYPTED_OUTPUT = Buffer.from('noirwire-test-ciphertext');
+
+// ciphertext_hash = SHA-256(payload) read little-endian with the top 3 bits cleared
+function ciphertextHash(payload) {
+    const digest = crypto.createHash('sha256').update(payload).digest();
+    digest[31] &= 0x1f;
+    return BigInt('0x' + Buffer.from(digest).reverse().toString('hex')).toString();
+}
+
 // Utility to convert bigint to string for JSON
 function bigintToString(obj) {
     if (typeof obj === 'bigint') {
@@ -136,7 +148,8 @@ async function main() {
     const shieldInput = {
         recipient_pk: "123456789",
         amount: "1000",
-        blinding: "42"
+        blinding: "42",
+        ciphertext_hash: ciphertextHash(TEST_ENCRYPTED_OUTPUT)
     };
 
     const shieldCommitment = computeCommitment(
@@ -203,7 +216,8 @@ async function main() {
         new_recipient_pk: transferNewPk.toString(),
         new_amount: transferNewAmount.toString(),
         new_blinding: transferNewBlinding.toString(),
-        fee: transferFee.toString()
+        fee: transferFee.toString(),
+        ciphertext_hash: ciphertextHash(TEST_ENCRYPTED_OUTPUT)
     };
 
     console.log(`   Root: ${transferRoot}`);
@@ -259,7 +273,13 @@ async function main() {
         recipient_lo: unshieldRecipientLo.toString(),
         recipient_hi: unshieldRecipientHi.toString(),
         public_amount: unshieldPublicAmount.toString(),
-        fee: unshieldFee.toString()
+        fee: unshieldFee.toString(),
+        // Opted out of association sets; paths are unconstrained
+        association_path: Array(20).fill('0'),
+        association_path_positions: Array(20).fill('0'),
+        association_root: '0',
+        // No post-withdrawal call
+        call_hash: '0'
     };
 
     console.log(`   Root: ${unshieldRoot}`);
@@ -268,6 +288,98 @@ async function main() {
     console.log(`   Recipient Hi: ${unshieldRecipientHi}`);
     console.log(`   Public amount: ${unshieldPublicAmount}\n`);
 
+    // ========== BATCH APPEND TEST VECTOR ==========
+    console.log('📝 Generating batch_append_input.json...');
+
+    // First batch of an empty depth-20 tree: 16 leaves at index 0
+    const batchDepth = 20;
+    const batchLog = 4;
+    const batchLeaves = [];
+    for (let i = 0; i < (1 << batchLog); i++) {
+        batchLeaves.push(computeCommitment(300n + BigInt(i), 100n, BigInt(i + 1)));
+    }
+
+    // Zero-subtree hashes: Z[0] = 0, Z[i+1] = H(Z[i], Z[i])
+    const hash2 = (l, r) => poseidon.F.toString(poseidon([l, r]));
+    const zeros = ['0'];
+    for (let i = 0; i < batchDepth; i++) {
+        zeros.push(hash2(zeros[i], zeros[i]));
+    }
+
+    let level = [...batchLeaves];
+    while (level.length > 1) {
+        const next = [];
+        for (let i = 0; i < level.length; i += 2) {
+            next.push(hash2(level[i], level[i + 1]));
+        }
+        level = next;
+    }
+
+    // Subtree 0 is always a left child with empty right siblings
+    const batchPath = zeros.slice(batchLog, batchDepth);
+    let batchNewRoot = level[0];
+    for (const sibling of batchPath) {
+        batchNewRoot = hash2(batchNewRoot, sibling);
+    }
+
+    const batchAppendInput = {
+        old_root: '0', // empty tree
+        new_root: batchNewRoot,
+        start_index: '0',
+        leaves: batchLeaves,
+        subtree_path: batchPath
+    };
+
+    console.log(`   New root: ${batchNewRoot}\n`);
+
+    // ========== DISCLOSURE TEST VECTOR ==========
+    console.log('📝 Generating disclosure_input.json...');
+
+    // Two notes addressed to the viewing key, two padding slots
+    const disclosureSize = 4;
+    const viewingSk = 777n;
+    const viewingKey = poseidon.F.toString(poseidon([viewingSk]));
+    const disclosedNotes = [
+        { amount: 400n, blinding: 11n },
+        { amount: 250n, blinding: 12n }
+    ];
+
+    const disclosureTree = new MerkleTree(poseidon, 20);
+    disclosureTree.insert(computeCommitment(103n, 800n, 4n));
+    const disclosedIndices = [];
+    for (const note of disclosedNotes) {
+        note.commitment = computeCommitment(BigInt(viewingKey), note.amount, note.blinding);
+        disclosedIndices.push(disclosureTree.leaves.length);
+        disclosureTree.insert(note.commitment);
+    }
+
+    const disclosureInput = {
+        root: disclosureTree.getRoot(),
+        viewing_key: viewingKey,
+        total: disclosedNotes.reduce((sum, note) => sum + note.amount, 0n).toString(),
+        commitments: [],
+        viewing_sk: viewingSk.toString(),
+        amounts: [],
+        blindings: [],
+        merkle_paths: [],
+        merkle_path_positions: []
+    };
+    for (let i = 0; i < disclosureSize; i++) {
+        const note = disclosedNotes[i];
+        // Padding: zero commitment and amount, paths are unconstrained
+        const proof = note
+            ? disclosureTree.getProof(disclosedIndices[i])
+            : { path: Array(20).fill(0n), positions: Array(20).fill(0) };
+        disclosureInput.commitments.push(note ? note.commitment : '0');
+        disclosureInput.amounts.push(note ? note.amount.toString() : '0');
+        disclosureInput.blindings.push(note ? note.blinding.toString() : '0');
+        disclosureInput.merkle_paths.push(proof.path.map(x => x.toString()));
+        disclosureInput.merkle_path_positions.push(proof.positions.map(x => x.toString()));
+    }
+
+    console.log(`   Viewing key: ${viewingKey}`);
+    console.log(`   Total: ${disclosureInput.total}\n`);
+
     // ========== WRITE FILES ==========
     // Ensure test_vectors directory exists
     if (!fs.existsSync(TEST_VECTORS_DIR)) {
@@ -278,7 +390,9 @@ async function main() {
     const files = {
         'shield_input.json': shieldInput,
         'transfer_input.json': transferInput,
-        'unshield_input.json': unshieldInput
+        'unshield_input.json': unshieldInput,
+        'batch_append_input.json': batchAppendInput,
+        'disclosure_input.json': disclosureInput
     };
     
     for (const [filename, data] of Object.entries(files)) {
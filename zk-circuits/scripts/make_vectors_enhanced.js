@@ -14,6 +14,7 @@
  */
 
 const { buildPoseidon } = require('circomlibjs');
+const crypto = require('crypto');
 const fs = require('fs');
 const path = require('path');
 
@@ -27,6 +28,16 @@ const NEGATIVE_TESTS_DIR = path.join(TEST_VECTORS_DIR, 'negative');
 const MAX_U64 = 2n ** 64n - 1n;
 const MAX_U128 = 2n ** 128n - 1n;
 
+// Sample encrypted note payload; tests submit the same bytes on-chain
+const TEST_ENCRYPTED_OUTPUT = Buffer.from('noirwire-test-ciphertext');
+
+// ciphertext_hash = SHA-256(payload) read little-endian with the top 3 bits cleared
+function ciphertextHash(payload) {
+    const digest = crypto.createHash('sha256').update(payload).digest();
+    digest[31] &= 0x1f;
+    return BigInt('0x' + Buffer.from(digest).reverse().toString('hex')).toString();
+}
+
 // Utility to convert bigint to string for JSON
 function bigintToString(obj) {
     if (typeof obj === 'bigint') {
@@ -161,7 +172,8 @@ async function main() {
     const shieldInput = {
         recipient_pk: "123456789",
         amount: "1000",
-        blinding: "42"
+        blinding: "42",
+        ciphertext_hash: ciphertextHash(TEST_ENCRYPTED_OUTPUT)
     };
     const shieldCommitment = computeCommitment(
         BigInt(shieldInput.recipient_pk),
@@ -205,7 +217,8 @@ async function main() {
         new_recipient_pk: transferNewPk.toString(),
         new_amount: transferNewAmount.toString(),
         new_blinding: transferNewBlinding.toString(),
-        fee: transferFee.toString()
+        fee: transferFee.toString(),
+        ciphertext_hash: ciphertextHash(TEST_ENCRYPTED_OUTPUT)
     };
     console.log(`   Root: ${transferRoot}`);
     console.log(`   Nullifier: ${transferNullifier}\n`);
@@ -243,12 +256,67 @@ async function main() {
         recipient_lo: recipient.lo.toString(),
         recipient_hi: recipient.hi.toString(),
         public_amount: unshieldPublicAmount.toString(),
-        fee: unshieldFee.toString()
+        fee: unshieldFee.toString(),
+        // Opted out of association sets; paths are unconstrained
+        association_path: Array(20).fill('0'),
+        association_path_positions: Array(20).fill('0'),
+        association_root: '0',
+        // No post-withdrawal call
+        call_hash: '0'
     };
     console.log(`   Root: ${unshieldRoot}`);
     console.log(`   Recipient Lo: ${recipient.lo}`);
     console.log(`   Recipient Hi: ${recipient.hi}\n`);
 
+    // Disclosure - two notes addressed to the viewing key, two padding slots
+    console.log('📝 disclosure_input.json (valid)');
+    const disclosureSize = 4;
+    const viewingSk = 777n;
+    const viewingKey = poseidon.F.toString(poseidon([viewingSk]));
+    const disclosedNotes = [
+        { amount: 400n, blinding: 11n },
+        { amount: 250n, blinding: 12n }
+    ];
+
+    // Index 0 holds a note for another key
+    const disclosureTree = new MerkleTree(poseidon, 20);
+    const foreignCommitment = computeCommitment(103n, 800n, 4n);
+    disclosureTree.insert(foreignCommitment);
+    const disclosedIndices = [];
+    for (const note of disclosedNotes) {
+        note.commitment = computeCommitment(BigInt(viewingKey), note.amount, note.blinding);
+        disclosedIndices.push(disclosureTree.leaves.length);
+        disclosureTree.insert(note.commitment);
+    }
+    const disclosureTotal = disclosedNotes.reduce((sum, note) => sum + note.amount, 0n);
+
+    const disclosureInput = {
+        root: disclosureTree.getRoot(),
+        viewing_key: viewingKey,
+        total: disclosureTotal.toString(),
+        commitments: [],
+        viewing_sk: viewingSk.toString(),
+        amounts: [],
+        blindings: [],
+        merkle_paths: [],
+        merkle_path_positions: []
+    };
+    for (let i = 0; i < disclosureSize; i++) {
+        const note = disclosedNotes[i];
+        // Padding: zero commitment and amount, paths are unconstrained
+        const proof = note
+            ? disclosureTree.getProof(disclosedIndices[i])
+            : { path: Array(20).fill(0n), positions: Array(20).fill(0) };
+        disclosureInput.commitments.push(note ? note.commitment : '0');
+        disclosureInput.amounts.push(note ? note.amount.toString() : '0');
+        disclosureInput.blindings.push(note ? note.blinding.toString() : '0');
+        disclosureInput.merkle_paths.push(proof.path.map(x => x.toString()));
+        disclosureInput.merkle_path_positions.push(proof.positions.map(x => x.toString()));
+    }
+    console.log(`   Root: ${disclosureInput.root}`);
+    console.log(`   Viewing key: ${viewingKey}`);
+    console.log(`   Total: ${disclosureTotal}\n`);
+
     // ========== NEGATIVE TEST VECTORS ==========
     console.log('❌ Generating negative (invalid) test vectors...\n');
 
@@ -309,13 +377,71 @@ async function main() {
         recipient_lo: (MAX_U128 + 1n).toString()
     };
 
+    // Unshield - claims an association set the note is not in
+    console.log('📝 unshield_wrong_association_root.json (should FAIL)');
+    const unshieldWrongAssociation = {
+        ...unshieldInput,
+        association_root: '12345'
+    };
+
+    // Replace slot i of the disclosure's per-note arrays
+    function withSlot(input, i, slot) {
+        const result = { ...input };
+        for (const [key, value] of Object.entries(slot)) {
+            result[key] = [...input[key]];
+            result[key][i] = value;
+        }
+        return result;
+    }
+
+    // Disclosure - prover does not hold the viewing key's secret
+    console.log('📝 disclosure_wrong_viewing_sk.json (should FAIL)');
+    const disclosureWrongSk = {
+        ...disclosureInput,
+        viewing_sk: (viewingSk + 1n).toString()
+    };
+
+    // Disclosure - total overstates the notes
+    console.log('📝 disclosure_total_mismatch.json (should FAIL)');
+    const disclosureTotalMismatch = {
+        ...disclosureInput,
+        total: (disclosureTotal + 1n).toString()
+    };
+
+    // Disclosure - lists a note in the tree that belongs to another key
+    console.log('📝 disclosure_foreign_note.json (should FAIL)');
+    const foreignProof = disclosureTree.getProof(0);
+    const disclosureForeignNote = withSlot(disclosureInput, 0, {
+        commitments: foreignCommitment,
+        amounts: '800',
+        blindings: '4',
+        merkle_paths: foreignProof.path.map(x => x.toString()),
+        merkle_path_positions: foreignProof.positions.map(x => x.toString())
+    });
+    disclosureForeignNote.total = (800n + disclosedNotes[1].amount).toString();
+
+    // Disclosure - note addressed to the viewing key but not in the tree
+    console.log('📝 disclosure_note_not_in_tree.json (should FAIL)');
+    const disclosureNotInTree = withSlot(disclosureInput, 2, {
+        commitments: computeCommitment(BigInt(viewingKey), 100n, 13n),
+        amounts: '100',
+        blindings: '13'
+    });
+    disclosureNotInTree.total = (disclosureTotal + 100n).toString();
+
+    // Disclosure - padding slot carries value to inflate the total
+    console.log('📝 disclosure_padding_with_amount.json (should FAIL)');
+    const disclosurePaddingAmount = withSlot(disclosureInput, 3, { amounts: '100' });
+    disclosurePaddingAmount.total = (disclosureTotal + 100n).toString();
+
     console.log('\n');
 
     // ========== WRITE FILES ==========
     const positiveFiles = {
         'shield_input.json': shieldInput,
         'transfer_input.json': transferInput,
-        'unshield_input.json': unshieldInput
+        'unshield_input.json': unshieldInput,
+        'disclosure_input.json': disclosureInput
     };
 
     const negativeFiles = {
@@ -325,7 +451,13 @@ async function main() {
         'transfer_fee_exceeds_amount.json': transferFeeExceeds,
         'transfer_amount_overflow.json': transferAmountOverflow,
         'unshield_fee_exceeds_amount.json': unshieldFeeExceeds,
-        'unshield_recipient_lo_overflow.json': unshieldRecipientOverflow
+        'unshield_recipient_lo_overflow.json': unshieldRecipientOverflow,
+        'unshield_wrong_association_root.json': unshieldWrongAssociation,
+        'disclosure_wrong_viewing_sk.json': disclosureWrongSk,
+        'disclosure_total_mismatch.json': disclosureTotalMismatch,
+        'disclosure_foreign_note.json': disclosureForeignNote,
+        'disclosure_note_not_in_tree.json': disclosureNotInTree,
+        'disclosure_padding_with_amount.json': disclosurePaddingAmount
     };
 
     console.log('💾 Writing positive test vectors...');
@@ -378,6 +510,30 @@ They test security properties like range checks, fee validation, and Merkle proo
    - Recipient lower limb exceeds 128 bits
    - Expected: Recipient encoding range check failure
 
+8. **unshield_wrong_association_root.json**
+   - Non-zero association_root the spent note is not in
+   - Expected: Association set membership failure
+
+9. **disclosure_wrong_viewing_sk.json**
+   - viewing_sk does not hash to viewing_key
+   - Expected: Viewing key check failure
+
+10. **disclosure_total_mismatch.json**
+   - total exceeds the sum of the disclosed amounts
+   - Expected: Total check failure
+
+11. **disclosure_foreign_note.json**
+   - Lists a note in the tree addressed to another recipient_pk
+   - Expected: Note commitment failure
+
+12. **disclosure_note_not_in_tree.json**
+   - Note addressed to the viewing key that was never inserted
+   - Expected: Merkle proof verification failure
+
+13. **disclosure_padding_with_amount.json**
+   - Zero (padding) commitment with a non-zero amount
+   - Expected: Padding amount check failure
+
 ## Usage
 
 These vectors are used to validate that the circuits correctly reject invalid inputs.
@@ -142,7 +142,13 @@ async function main() {
         { circuit: 'transfer', file: 'transfer_fee_exceeds_amount.json' },
         { circuit: 'transfer', file: 'transfer_amount_overflow.json' },
         { circuit: 'unshield', file: 'unshield_fee_exceeds_amount.json' },
-        { circuit: 'unshield', file: 'unshield_recipient_lo_overflow.json' }
+        { circuit: 'unshield', file: 'unshield_recipient_lo_overflow.json' },
+        { circuit: 'unshield', file: 'unshield_wrong_association_root.json' },
+        { circuit: 'disclosure', file: 'disclosure_wrong_viewing_sk.json' },
+        { circuit: 'disclosure', file: 'disclosure_total_mismatch.json' },
+        { circuit: 'disclosure', file: 'disclosure_foreign_note.json' },
+        { circuit: 'disclosure', file: 'disclosure_note_not_in_tree.json' },
+        { circuit: 'disclosure', file: 'disclosure_padding_with_amount.json' }
     ];
     
     const results = {
@@ -18,7 +18,7 @@ const crypto = require('crypto');
 const { execSync } = require('child_process');
 
 // Configuration - relative to this script's location
-const CIRCUITS = ['shield', 'transfer', 'unshield'];
+const CIRCUITS = ['shield', 'transfer', 'unshield', 'batch_append', 'disclosure'];
 const SCRIPT_DIR = __dirname;
 const PROJECT_ROOT = path.resolve(SCRIPT_DIR, '..');
 const BUILD_DIR = path.join(PROJECT_ROOT, 'build');
@@ -174,7 +174,11 @@ for (const circuit of CIRCUITS) {
     const publicPath = path.join(circuitDir, 'public.json');
     if (fs.existsSync(publicPath)) {
         const publicSignals = JSON.parse(fs.readFileSync(publicPath, 'utf8'));
-        const expectedCount = circuit === 'shield' ? 1 : circuit === 'transfer' ? 4 : 5;
+        const expectedCount = circuit === 'shield' ? 3
+            : circuit === 'transfer' ? 5
+            : circuit === 'batch_append' ? 19
+            : circuit === 'disclosure' ? 7
+            : 8; // unshield
         
         if (publicSignals.length === expectedCount) {
             console.log(`  ${GREEN}✓${RESET} Public signals count correct (${expectedCount})`);
@@ -191,13 +195,13 @@ for (const circuit of CIRCUITS) {
 
 // Check Powers of Tau
 console.log(`${BLUE}Checking Powers of Tau...${RESET}`);
-const ptauPath = path.join(POT_DIR, 'pot14_final.ptau');
+const ptauPath = path.join(POT_DIR, 'pot15_final.ptau');
 if (fs.existsSync(ptauPath)) {
     const stats = fs.statSync(ptauPath);
-    console.log(`  ${GREEN}✓${RESET} pot14_final.ptau (${formatBytes(stats.size)})`);
+    console.log(`  ${GREEN}✓${RESET} pot15_final.ptau (${formatBytes(stats.size)})`);
     console.log(`     Located at: ${path.relative(PROJECT_ROOT, ptauPath)}`);
 } else {
-    console.log(`  ${RED}✗${RESET} pot14_final.ptau - MISSING`);
+    console.log(`  ${RED}✗${RESET} pot15_final.ptau - MISSING`);
     console.log(`     Expected at: ${path.relative(PROJECT_ROOT, ptauPath)}`);
     allPassed = false;
 }
@@ -210,6 +214,8 @@ const sourceFiles = [
     'shield.circom',
     'transfer.circom',
     'unshield.circom',
+    'batch_append.circom',
+    'disclosure.circom',
     'merkle/merkle.circom'
 ];
 for (const file of sourceFiles) {
@@ -0,0 +1,104 @@
+pragma circom 2.0.0;
+
+include "./common.circom";
+include "./merkle/merkle.circom";
+
+/*
+ * BatchAppend Circuit
+ *
+ * Appends a batch of N = 2^BATCH_LOG queued commitments to the commitment tree.
+ * The batch always fills one complete, previously empty subtree of height
+ * BATCH_LOG, so start_index must be a multiple of N.
+ * Proves:
+ *   - The subtree at start_index is empty under old_root
+ *   - Writing the leaves into that subtree yields new_root
+ *
+ * Empty nodes are zero-subtree hashes: Z[0] = 0, Z[i+1] = Poseidon(Z[i], Z[i]).
+ * old_root = 0 denotes the empty tree (the program cannot compute Poseidon
+ * on-chain); in that case start_index must be 0.
+ *
+ * PRIVATE INPUTS:
+ *   - subtree_path[DEPTH - BATCH_LOG]: Sibling hashes from the subtree root to the tree root
+ *
+ * PUBLIC INPUTS (in order):
+ *   1. old_root: Tree root before the batch (0 = empty tree)
+ *   2. new_root: Tree root after the batch
+ *   3. start_index: Leaf index of the first appended leaf
+ *   4. leaves[N]: Appended commitments (zero for padding)
+ *
+ * CONSTRAINTS:
+ *   - start_index < 2^DEPTH and start_index % N == 0
+ *   - old_root recomputed from an empty subtree (or empty-tree siblings when old_root = 0)
+ *   - new_root recomputed from the leaves subtree along the same path
+ */
+template BatchAppend(DEPTH, BATCH_LOG) {
+    var N = 1 << BATCH_LOG;
+    var PATH = DEPTH - BATCH_LOG;
+
+    signal input old_root;
+    signal input new_root;
+    signal input start_index;
+    signal input leaves[N];
+
+    signal input subtree_path[PATH];
+
+    // 1. Zero-subtree hashes per level
+    signal zeros[DEPTH];
+    component zeroHashers[DEPTH - 1];
+    zeros[0] <== 0;
+    for (var i = 0; i < DEPTH - 1; i++) {
+        zeroHashers[i] = Poseidon(2);
+        zeroHashers[i].inputs[0] <== zeros[i];
+        zeroHashers[i].inputs[1] <== zeros[i];
+        zeros[i + 1] <== zeroHashers[i].out;
+    }
+
+    // 2. Decompose start_index; the low BATCH_LOG bits must be zero and the
+    //    remaining bits give the subtree position
+    component indexBits = Num2Bits(DEPTH);
+    indexBits.in <== start_index;
+    for (var i = 0; i < BATCH_LOG; i++) {
+        indexBits.out[i] === 0;
+    }
+
+    // 3. Hash the leaves into the subtree root (heap layout: nodes[N + k] = H(nodes[2k], nodes[2k + 1]))
+    signal nodes[2 * N - 1];
+    component nodeHashers[N - 1];
+    for (var i = 0; i < N; i++) {
+        nodes[i] <== leaves[i];
+    }
+    for (var k = 0; k < N - 1; k++) {
+        nodeHashers[k] = Poseidon(2);
+        nodeHashers[k].inputs[0] <== nodes[2 * k];
+        nodeHashers[k].inputs[1] <== nodes[2 * k + 1];
+        nodes[N + k] <== nodeHashers[k].out;
+    }
+
+    // 4. Recompute both roots along the same subtree path
+    component oldTree = MerkleTreeInclusionProof(PATH);
+    component newTree = MerkleTreeInclusionProof(PATH);
+    oldTree.leaf <== zeros[BATCH_LOG];
+    newTree.leaf <== nodes[2 * N - 2];
+    for (var i = 0; i < PATH; i++) {
+        oldTree.path_elements[i] <== subtree_path[i];
+        oldTree.path_indices[i] <== indexBits.out[BATCH_LOG + i];
+        newTree.path_elements[i] <== subtree_path[i];
+        newTree.path_indices[i] <== indexBits.out[BATCH_LOG + i];
+    }
+
+    // 5. Old root: either matches, or the tree is empty and every sibling is a zero subtree
+    component isEmpty = IsZero();
+    isEmpty.in <== old_root;
+
+    (1 - isEmpty.out) * (oldTree.root - old_root) === 0;
+    isEmpty.out * start_index === 0;
+    for (var i = 0; i < PATH; i++) {
+        isEmpty.out * (subtree_path[i] - zeros[BATCH_LOG + i]) === 0;
+    }
+
+    // 6. New root
+    new_root === newTree.root;
+}
+
+// Main component - depth 20, batches of 16 leaves
+component main {public [old_root, new_root, start_index, leaves]} = BatchAppend(20, 4);
@@ -0,0 +1,103 @@
+pragma circom 2.0.0;
+
+include "./common.circom";
+include "./merkle/merkle.circom";
+
+/*
+ * Disclosure Circuit
+ *
+ * Proves to an auditor that up to N notes belong to a viewing key and sum to
+ * a total, without spending them.
+ * Proves:
+ *   - Knowledge of viewing_sk with viewing_key = Poseidon(viewing_sk)
+ *   - Each listed commitment opens to recipient_pk = viewing_key
+ *   - Each listed commitment is included in the tree under root
+ *   - The note amounts sum to total
+ *
+ * Only notes whose recipient_pk was derived as Poseidon(viewing_sk) can be
+ * disclosed; the spend circuits leave recipient_pk free, see ABI.md.
+ *
+ * Zero commitments are padding: their amount must be 0 and they skip the
+ * opening and inclusion checks. Duplicate commitments are rejected on-chain.
+ *
+ * PRIVATE INPUTS:
+ *   - viewing_sk: Secret behind the viewing key
+ *   - amounts[N]: Note amounts (each 0 <= amount < 2^64)
+ *   - blindings[N]: Note blinding factors
+ *   - merkle_paths[N][DEPTH]: Sibling hashes along each note's path to root
+ *   - merkle_path_positions[N][DEPTH]: Left/right positions (0/1)
+ *
+ * PUBLIC INPUTS (in order):
+ *   1. root: Merkle root the notes are included under
+ *   2. viewing_key: Poseidon(viewing_sk), the recipient_pk of the notes
+ *   3. total: Sum of the disclosed amounts (must be < 2^64)
+ *   4. commitments[N]: Disclosed note commitments (zero for padding)
+ *
+ * CONSTRAINTS:
+ *   - viewing_key == Poseidon(viewing_sk)
+ *   - For non-padding i: commitments[i] == Poseidon(viewing_key, amounts[i], blindings[i])
+ *     and the inclusion proof of commitments[i] yields root
+ *   - For padding i: amounts[i] == 0
+ *   - Range checks: 0 <= amounts[i], total < 2^64
+ *   - total == sum(amounts)
+ */
+template Disclosure(DEPTH, N) {
+    signal input root;
+    signal input viewing_key;
+    signal input total;
+    signal input commitments[N];
+
+    signal input viewing_sk;
+    signal input amounts[N];
+    signal input blindings[N];
+    signal input merkle_paths[N][DEPTH];
+    signal input merkle_path_positions[N][DEPTH];
+
+    // 1. The prover holds the viewing key's secret
+    component keyHasher = Poseidon(1);
+    keyHasher.inputs[0] <== viewing_sk;
+    viewing_key === keyHasher.out;
+
+    component isPadding[N];
+    component amountChecks[N];
+    component notes[N];
+    component trees[N];
+    signal partial[N + 1];
+    partial[0] <== 0;
+
+    for (var i = 0; i < N; i++) {
+        isPadding[i] = IsZero();
+        isPadding[i].in <== commitments[i];
+
+        // 2. Amounts are range checked; padding carries no value
+        amountChecks[i] = AmountRangeCheck();
+        amountChecks[i].amount <== amounts[i];
+        isPadding[i].out * amounts[i] === 0;
+
+        // 3. The note is addressed to the viewing key
+        notes[i] = NoteCommitment();
+        notes[i].recipient_pk <== viewing_key;
+        notes[i].amount <== amounts[i];
+        notes[i].blinding <== blindings[i];
+        (1 - isPadding[i].out) * (notes[i].commitment - commitments[i]) === 0;
+
+        // 4. The note is in the tree
+        trees[i] = MerkleTreeInclusionProof(DEPTH);
+        trees[i].leaf <== commitments[i];
+        for (var j = 0; j < DEPTH; j++) {
+            trees[i].path_elements[j] <== merkle_paths[i][j];
+            trees[i].path_indices[j] <== merkle_path_positions[i][j];
+        }
+        (1 - isPadding[i].out) * (trees[i].root - root) === 0;
+
+        partial[i + 1] <== partial[i] + amounts[i];
+    }
+
+    // 5. Total matches and fits in a u64
+    component totalCheck = AmountRangeCheck();
+    totalCheck.amount <== total;
+    total === partial[N];
+}
+
+// Main component - depth 20, up to 4 notes per disclosure
+component main {public [root, viewing_key, total, commitments]} = Disclosure(20, 4);
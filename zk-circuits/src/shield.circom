@@ -10,11 +10,17 @@ include "./common.circom";
  * 
  * PRIVATE INPUTS:
  *   - recipient_pk: Public key of the note recipient
- *   - amount: Note amount (integer, no decimals, must be 0 <= amount < 2^64)
  *   - blinding: Random blinding factor
  * 
- * PUBLIC OUTPUTS (in order):
+ * PUBLIC INPUTS:
+ *   - amount: Note amount (integer, no decimals, must be 0 <= amount < 2^64),
+ *     deposited into the treasury by the program
+ *   - ciphertext_hash: Hash of the encrypted note emitted on-chain
+ * 
+ * PUBLIC SIGNALS (in order):
  *   1. commitment: The note commitment
+ *   2. amount: Deposited amount
+ *   3. ciphertext_hash: Binds the proof to the encrypted note payload
  * 
  * CONSTRAINTS:
  *   - Computes commitment = Poseidon(recipient_pk, amount, blinding)
@@ -27,9 +33,14 @@ include "./common.circom";
 template Shield() {
     // Private inputs
     signal input recipient_pk;
-    signal input amount;
     signal input blinding;
 
+    // Public input - deposited amount, funded by the depositor on-chain
+    signal input amount;
+
+    // Public input - hash of the encrypted note (see ABI.md)
+    signal input ciphertext_hash;
+
     // Public output
     signal output commitment;
 
@@ -44,7 +55,12 @@ template Shield() {
     noteCommitment.blinding <== blinding;
 
     commitment <== noteCommitment.commitment;
+
+    // Bind ciphertext_hash into the constraint system so the proof cannot
+    // be replayed with a different payload
+    signal ciphertext_hash_sq;
+    ciphertext_hash_sq <== ciphertext_hash * ciphertext_hash;
 }
 
-// Main component - commitment will be public by default (it's the only output)
-component main = Shield();
+// Main component - output commitment first, then the public inputs in declaration order
+component main {public [amount, ciphertext_hash]} = Shield();
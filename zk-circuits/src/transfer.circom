@@ -27,11 +27,15 @@ include "./merkle/merkle.circom";
  *   - new_blinding: Blinding of output note
  *   - fee: Transaction fee (must be 0 <= fee <= old_amount)
  * 
- * PUBLIC OUTPUTS (in order):
+ * PUBLIC INPUTS:
+ *   - ciphertext_hash: Hash of the encrypted output note emitted on-chain
+ * 
+ * PUBLIC SIGNALS (in order):
  *   1. root: Merkle root
  *   2. nullifier: Computed nullifier
  *   3. new_commitment: Output note commitment
  *   4. fee: Transaction fee
+ *   5. ciphertext_hash: Binds the proof to the encrypted output payload
  * 
  * CONSTRAINTS:
  *   - Recomputes old_commitment from inputs
@@ -66,6 +70,9 @@ template Transfer(DEPTH) {
     // Private input - fee
     signal input fee;
 
+    // Public input - hash of the encrypted output note (see ABI.md)
+    signal input ciphertext_hash;
+
     // Public outputs
     signal output root;
     signal output nullifier;
@@ -120,7 +127,11 @@ template Transfer(DEPTH) {
 
     // 6. Output fee as public signal
     fee_output <== fee;
+
+    // 7. Bind ciphertext_hash into the constraint system
+    signal ciphertext_hash_sq;
+    ciphertext_hash_sq <== ciphertext_hash * ciphertext_hash;
 }
 
-// Main component - all outputs will be public signals
-component main = Transfer(20);
+// Main component - outputs first, then the public ciphertext_hash
+component main {public [ciphertext_hash]} = Transfer(20);
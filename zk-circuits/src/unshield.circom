@@ -21,14 +21,22 @@ include "./merkle/merkle.circom";
  *   - recipient_hi: Upper 128 bits of public recipient address
  *   - public_amount: Amount to send to recipient (must be 0 <= public_amount < 2^64)
  *   - fee: Transaction fee (must be 0 <= fee <= old_amount)
+ *   - association_path[DEPTH]: Sibling hashes in the association set tree
+ *   - association_path_positions[DEPTH]: Left/right positions (0/1)
  * 
- * PUBLIC OUTPUTS (in order):
+ * PUBLIC INPUTS:
+ *   - association_root: Root of an approved association set (0 = opted out)
+ *   - call_hash: Hash of the post-withdrawal call (0 = no call)
+ * 
+ * PUBLIC SIGNALS (in order):
  *   1. root: Merkle root
  *   2. nullifier: Computed nullifier
  *   3. recipient_lo: Lower 128 bits of recipient address
  *   4. recipient_hi: Upper 128 bits of recipient address
  *   5. public_amount: Amount to send to recipient
  *   6. fee: Transaction fee
+ *   7. association_root: Association set the spent note belongs to (0 = opted out)
+ *   8. call_hash: Binds the proof to a target program and instruction data (0 = no call)
  * 
  * CONSTRAINTS:
  *   - Recomputes old_commitment from inputs
@@ -38,6 +46,12 @@ include "./merkle/merkle.circom";
  *   - Range checks: 0 <= old_amount, public_amount, fee < 2^64
  *   - Recipient encoding: each limb fits in 128 bits
  *   - Fee constraint: fee <= old_amount
+ *   - If association_root != 0: old_commitment is included in the association set tree
+ * 
+ * ASSOCIATION SETS:
+ *   An association set is a Merkle tree (same depth and hashing as the note tree) of
+ *   note commitments approved by the pool curator. Proving membership dissociates the
+ *   withdrawal from deposits outside the set without revealing which note is spent.
  * 
  * RECIPIENT ENCODING:
  *   Solana addresses are 32 bytes (256 bits).
@@ -70,6 +84,16 @@ template Unshield(DEPTH) {
     signal input public_amount;
     signal input fee;
 
+    // Private inputs - association set membership proof
+    signal input association_path[DEPTH];
+    signal input association_path_positions[DEPTH];
+
+    // Public input - association set root (see ABI.md)
+    signal input association_root;
+
+    // Public input - post-withdrawal call hash (see ABI.md)
+    signal input call_hash;
+
     // Public outputs
     signal output root;
     signal output nullifier;
@@ -127,7 +151,23 @@ template Unshield(DEPTH) {
     recipient_hi_output <== recipient_hi;
     amount_output <== public_amount;
     fee_output <== fee;
+
+    // 6. Association set membership, skipped when association_root = 0
+    component associationProof = MerkleTreeInclusionProof(DEPTH);
+    associationProof.leaf <== old_commitment;
+    for (var i = 0; i < DEPTH; i++) {
+        associationProof.path_elements[i] <== association_path[i];
+        associationProof.path_indices[i] <== association_path_positions[i];
+    }
+    component optedOut = IsZero();
+    optedOut.in <== association_root;
+    (1 - optedOut.out) * (associationProof.root - association_root) === 0;
+
+    // 7. Bind call_hash into the constraint system so the proof cannot
+    // be replayed with a different target program or instruction data
+    signal call_hash_sq;
+    call_hash_sq <== call_hash * call_hash;
 }
 
-// Main component - all outputs will be public signals
-component main = Unshield(20);
+// Main component - outputs first, then the public association_root and call_hash
+component main {public [association_root, call_hash]} = Unshield(20);